@@ -0,0 +1,59 @@
+//! Little-endian helpers for encoding and decoding configuration blocks
+
+/// Read a little-endian `u16` from `bytes`, starting at `offset`
+pub(crate) const fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+/// Read a little-endian `u32` from `bytes`, starting at `offset`
+pub(crate) const fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+/// Copy `N` bytes from `bytes`, starting at `offset`
+pub(crate) const fn read_array<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    let mut array = [0; N];
+    let mut idx = 0;
+    while idx < N {
+        array[idx] = bytes[offset + idx];
+        idx += 1;
+    }
+    array
+}
+
+/// Write `value` into `bytes` as a little-endian `u16`, starting at `offset`
+pub(crate) const fn write_u16<const N: usize>(
+    bytes: [u8; N],
+    offset: usize,
+    value: u16,
+) -> [u8; N] {
+    write_array(bytes, offset, &value.to_le_bytes())
+}
+
+/// Write `value` into `bytes` as a little-endian `u32`, starting at `offset`
+pub(crate) const fn write_u32<const N: usize>(
+    bytes: [u8; N],
+    offset: usize,
+    value: u32,
+) -> [u8; N] {
+    write_array(bytes, offset, &value.to_le_bytes())
+}
+
+/// Copy all of `src` into `bytes`, starting at `offset`
+pub(crate) const fn write_array<const N: usize>(
+    mut bytes: [u8; N],
+    offset: usize,
+    src: &[u8],
+) -> [u8; N] {
+    let mut idx = 0;
+    while idx < src.len() {
+        bytes[offset + idx] = src[idx];
+        idx += 1;
+    }
+    bytes
+}
@@ -0,0 +1,371 @@
+//! Device configuration data (DCD)
+//!
+//! The DCD is a list of commands that the boot ROM executes before it jumps
+//! to the image. Use the DCD to initialize peripherals, like SEMC SDRAM, that
+//! need to be ready before your program runs.
+//!
+//! Use a [`Builder`] to create a DCD. The builder computes the DCD header for
+//! you, and merges consecutive write commands that share the same width and
+//! operation. If the DCD grows larger than the ROM's maximum DCD size,
+//! [`MAX_DCD_SIZE`], you'll observe a compile-time error.
+//!
+//! Once you've added all commands, size a `static` array with
+//! [`Builder::len`], then [`build`](Builder::build) the DCD into the array.
+//! Provide the array's absolute address to the
+//! [`ImageVectorTable`](crate::ivt::ImageVectorTable) with
+//! [`dcd`](crate::ivt::ImageVectorTable::dcd).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use imxrt_boot_gen::dcd::{self, Condition, Width};
+//! use imxrt_boot_gen::ivt::ImageVectorTable;
+//!
+//! const CCM_CCGR3: u32 = 0x400F_C074;
+//! const SEMC_MCR: u32 = 0x402F_0000;
+//! const SEMC_INTR: u32 = 0x402F_003C;
+//!
+//! const DCD_BUILDER: dcd::Builder = dcd::Builder::new()
+//!     .set(Width::Word, CCM_CCGR3, 0x3 << 2)
+//!     .clear(Width::Word, SEMC_MCR, 1 << 1)
+//!     .check(Width::Word, Condition::AnySet, SEMC_INTR, 1);
+//!
+//! const DCD_ADDRESS: u32 = 0x6000_1030;
+//!
+//! #[no_mangle]
+//! #[link_section = ".dcd"]
+//! static DCD: [u8; DCD_BUILDER.len()] = DCD_BUILDER.build();
+//!
+//! #[no_mangle]
+//! #[link_section = ".ivt"]
+//! static IMAGE_VECTOR_TABLE: ImageVectorTable =
+//!     ImageVectorTable::new(0x6000_2000).dcd(DCD_ADDRESS);
+//! ```
+
+/// DCD header tag
+const DCD_TAG: u8 = 0xD2;
+/// DCD header version
+const DCD_VERSION: u8 = 0x41;
+
+/// Write data command tag
+const WRITE_DATA_TAG: u8 = 0xCC;
+/// Check data command tag
+const CHECK_DATA_TAG: u8 = 0xCF;
+/// NOP command tag
+const NOP_TAG: u8 = 0xC0;
+
+/// `MASK` command parameter flag
+const FLAG_MASK: u8 = 1 << 3;
+/// `SET` command parameter flag
+const FLAG_SET: u8 = 1 << 4;
+
+/// Size of a header, in bytes
+const HEADER_SIZE: usize = 4;
+
+/// The maximum size of a DCD, in bytes, including its header
+pub const MAX_DCD_SIZE: usize = 1768;
+
+/// The width of a register access
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Width {
+    /// One byte
+    Byte = 1,
+    /// Two bytes
+    HalfWord = 2,
+    /// Four bytes
+    Word = 4,
+}
+
+/// The condition of a check command
+///
+/// The ROM polls the address until the condition is true for the
+/// bits in the mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Condition {
+    /// All bits in the mask are clear
+    AllClear = 0,
+    /// Any bit in the mask is clear
+    AnyClear = FLAG_MASK,
+    /// All bits in the mask are set
+    AllSet = FLAG_SET,
+    /// Any bit in the mask is set
+    AnySet = FLAG_MASK | FLAG_SET,
+}
+
+/// A DCD builder
+///
+/// Commands are added in the order that the ROM executes them. All values are
+/// serialized in big-endian byte order, as required by the ROM.
+///
+/// See the [module-level documentation](crate::dcd) for an example.
+#[derive(Clone, Copy)]
+pub struct Builder {
+    bytes: [u8; MAX_DCD_SIZE],
+    len: usize,
+    /// Offset of the most recent command, if it is a write data command
+    last_write: Option<usize>,
+}
+
+impl Builder {
+    /// Create a new DCD builder that has no commands
+    pub const fn new() -> Self {
+        Builder {
+            bytes: [0; MAX_DCD_SIZE],
+            len: HEADER_SIZE,
+            last_write: None,
+        }
+    }
+
+    /// Write `value` to the register at `address`
+    pub const fn write(self, width: Width, address: u32, value: u32) -> Self {
+        self.write_data(width as u8, address, value)
+    }
+
+    /// Set the bits in `mask` in the register at `address`
+    ///
+    /// The ROM performs a read-modify-write of the register.
+    pub const fn set(self, width: Width, address: u32, mask: u32) -> Self {
+        self.write_data(width as u8 | FLAG_MASK | FLAG_SET, address, mask)
+    }
+
+    /// Clear the bits in `mask` in the register at `address`
+    ///
+    /// The ROM performs a read-modify-write of the register.
+    pub const fn clear(self, width: Width, address: u32, mask: u32) -> Self {
+        self.write_data(width as u8 | FLAG_MASK, address, mask)
+    }
+
+    /// Poll the register at `address` until `condition` is true for the bits in `mask`
+    ///
+    /// The ROM polls until the condition is true. See [`check_count`](Builder::check_count)
+    /// to limit the number of polls.
+    pub const fn check(self, width: Width, condition: Condition, address: u32, mask: u32) -> Self {
+        self.check_data(width, condition, address, mask, None)
+    }
+
+    /// Poll the register at `address`, at most `count` times, until `condition` is true
+    /// for the bits in `mask`
+    ///
+    /// If the condition is not met after `count` polls, the ROM abandons the DCD.
+    pub const fn check_count(
+        self,
+        width: Width,
+        condition: Condition,
+        address: u32,
+        mask: u32,
+        count: u32,
+    ) -> Self {
+        self.check_data(width, condition, address, mask, Some(count))
+    }
+
+    /// Insert a NOP command
+    pub const fn nop(self) -> Self {
+        let mut builder = self.reserve(HEADER_SIZE);
+        builder = builder.push_header(NOP_TAG, HEADER_SIZE as u16, 0);
+        builder.last_write = None;
+        builder
+    }
+
+    /// Returns the size of the DCD, in bytes, including its header
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the DCD has no commands
+    pub const fn is_empty(&self) -> bool {
+        self.len == HEADER_SIZE
+    }
+
+    /// Create the DCD
+    ///
+    /// `N` must equal [`len`](Builder::len). Otherwise, you'll observe a compile-time
+    /// error.
+    pub const fn build<const N: usize>(self) -> [u8; N] {
+        if N != self.len {
+            panic!("DCD array size does not match the DCD length");
+        }
+        let header = Builder { len: 0, ..self }.push_header(DCD_TAG, self.len as u16, DCD_VERSION);
+
+        let mut dcd = [0; N];
+        let mut idx = 0;
+        while idx < N {
+            dcd[idx] = header.bytes[idx];
+            idx += 1;
+        }
+        dcd
+    }
+
+    const fn write_data(self, parameters: u8, address: u32, value: u32) -> Self {
+        let mut builder = self.reserve(8);
+        builder = match builder.last_write {
+            Some(offset) if builder.bytes[offset + 3] == parameters => {
+                let length =
+                    u16::from_be_bytes([builder.bytes[offset + 1], builder.bytes[offset + 2]]) + 8;
+                let length = length.to_be_bytes();
+                builder.bytes[offset + 1] = length[0];
+                builder.bytes[offset + 2] = length[1];
+                builder
+            }
+            _ => {
+                let builder = builder.reserve(HEADER_SIZE + 8);
+                let offset = builder.len;
+                let mut builder =
+                    builder.push_header(WRITE_DATA_TAG, (HEADER_SIZE + 8) as u16, parameters);
+                builder.last_write = Some(offset);
+                builder
+            }
+        };
+        builder.push_u32(address).push_u32(value)
+    }
+
+    const fn check_data(
+        self,
+        width: Width,
+        condition: Condition,
+        address: u32,
+        mask: u32,
+        count: Option<u32>,
+    ) -> Self {
+        let length = match count {
+            Some(_) => HEADER_SIZE + 12,
+            None => HEADER_SIZE + 8,
+        };
+        let mut builder = self.reserve(length).push_header(
+            CHECK_DATA_TAG,
+            length as u16,
+            width as u8 | condition as u8,
+        );
+        builder = builder.push_u32(address).push_u32(mask);
+        if let Some(count) = count {
+            builder = builder.push_u32(count);
+        }
+        builder.last_write = None;
+        builder
+    }
+
+    /// Panics if there isn't room for `size` more bytes
+    const fn reserve(self, size: usize) -> Self {
+        if self.len + size > MAX_DCD_SIZE {
+            panic!("DCD exceeds the maximum DCD size");
+        }
+        self
+    }
+
+    const fn push_header(self, tag: u8, length: u16, parameters: u8) -> Self {
+        let length = length.to_be_bytes();
+        self.push_u32(u32::from_be_bytes([tag, length[0], length[1], parameters]))
+    }
+
+    const fn push_u32(mut self, value: u32) -> Self {
+        let bytes = value.to_be_bytes();
+        let mut idx = 0;
+        while idx < bytes.len() {
+            self.bytes[self.len + idx] = bytes[idx];
+            idx += 1;
+        }
+        self.len += bytes.len();
+        self
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Builder, Condition, Width, MAX_DCD_SIZE};
+
+    #[test]
+    fn empty() {
+        const DCD: [u8; 4] = Builder::new().build();
+        assert_eq!(DCD, [0xD2, 0x00, 0x04, 0x41]);
+    }
+
+    #[test]
+    fn merge_writes() {
+        const BUILDER: Builder = Builder::new()
+            .write(Width::Word, 0x1000, 1)
+            .write(Width::Word, 0x1004, 2)
+            .set(Width::Word, 0x1008, 4);
+        const DCD: [u8; BUILDER.len()] = BUILDER.build();
+        assert_eq!(
+            DCD,
+            [
+                0xD2, 0x00, 0x24, 0x41, // DCD header
+                0xCC, 0x00, 0x14, 0x04, // write
+                0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x01, //
+                0x00, 0x00, 0x10, 0x04, 0x00, 0x00, 0x00, 0x02, //
+                0xCC, 0x00, 0x0C, 0x1C, // set
+                0x00, 0x00, 0x10, 0x08, 0x00, 0x00, 0x00, 0x04, //
+            ]
+        );
+    }
+
+    #[test]
+    fn check_count() {
+        const BUILDER: Builder =
+            Builder::new().check_count(Width::HalfWord, Condition::AllClear, 0x2000, 0x80, 16);
+        const DCD: [u8; BUILDER.len()] = BUILDER.build();
+        assert_eq!(
+            DCD,
+            [
+                0xD2, 0x00, 0x14, 0x41, // DCD header
+                0xCF, 0x00, 0x10, 0x02, // check
+                0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x10,
+            ]
+        );
+    }
+
+    #[test]
+    fn maximum_size() {
+        // Header, one write command header, and 220 address / value pairs
+        let mut builder = Builder::new();
+        for idx in 0..220 {
+            builder = builder.write(Width::Word, idx, idx);
+        }
+        assert_eq!(builder.len(), MAX_DCD_SIZE);
+    }
+}
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::dcd::{Builder, Width, MAX_DCD_SIZE};
+/// const fn fill(mut builder: Builder, count: u32) -> Builder {
+///     let mut idx = 0;
+///     while idx < count {
+///         builder = builder.write(Width::Word, idx, idx);
+///         idx += 1;
+///     }
+///     builder
+/// }
+/// const DCD: Builder = fill(Builder::new(), 220);
+/// const _: () = assert!(DCD.len() == MAX_DCD_SIZE);
+/// ```
+#[cfg(doctest)]
+struct BuilderMaximumSize;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::dcd::{Builder, Width};
+/// const fn fill(mut builder: Builder, count: u32) -> Builder {
+///     let mut idx = 0;
+///     while idx < count {
+///         builder = builder.write(Width::Word, idx, idx);
+///         idx += 1;
+///     }
+///     builder
+/// }
+/// const DCD: Builder = fill(Builder::new(), 221); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct BuilderTooLarge;
@@ -55,13 +55,30 @@
 //! a [`ConfigurationBlock`]. See the `ConfigurationBlock` documentation
 //! for more information.
 
+mod check;
 mod fields;
 mod lookup;
+#[doc(hidden)]
+pub mod macros;
+pub mod pad_setting;
+mod parse;
 mod sequence;
+mod xip;
 
+use core::fmt;
+
+use crate::bytes::{read_array, read_u16, read_u32, write_array, write_u16, write_u32};
+
+pub use check::{LutError, LutErrorKind, LutErrors};
 pub use fields::*;
-pub use lookup::{Command, LookupTable};
-pub use sequence::{opcodes, Instr, Pads, Sequence, SequenceBuilder, JUMP_ON_CS, STOP};
+pub use lookup::{Command, LookupTable, NandCommand, SequenceError, SequenceErrors, Sequences};
+pub use pad_setting::PadSetting;
+pub use parse::{ParseError, ParseErrorKind};
+pub use sequence::{
+    opcodes, AddressBits, Instr, Instructions, MultiSequence, MultiSequenceBuilder, Opcode, Pads,
+    Sequence, SequenceBuilder, JUMP_ON_CS, STOP,
+};
+pub use xip::ContinuousRead;
 
 /// ASCII 'FCFB'
 const TAG: u32 = 0x4246_4346;
@@ -70,6 +87,8 @@ const TAG: u32 = 0x4246_4346;
 /// [23:16] major = 1
 /// [31:24] ascii ‘V’
 const VERSION: u32 = 0x5601_0000;
+/// Decoding accepts any minor and bugfix version
+const VERSION_MASK: u32 = 0xFFFF_0000;
 
 /// The recommended `csHoldTime`, `0x03`.
 ///
@@ -111,16 +130,16 @@ pub const RECOMMENDED_CS_SETUP_TIME: u8 = 0x03;
 ///         .serial_clk_freq(SerialClockFrequency::MHz60)
 ///         .serial_flash_pad_type(FlashPadType::Quad);
 ///
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(C, packed)]
 pub struct ConfigurationBlock {
     tag: u32,
     version: u32,
     _reserved0: [u8; 4], // 0x008
-    read_sample_clk_src: u8,
+    read_sample_clk_src: ReadSampleClockSource,
     cs_hold_time: u8,
     cs_setup_time: u8,
-    column_address_width: u8,
+    column_address_width: ColumnAddressWidth,
     device_mode_configuration: u8,
     _reserved1: [u8; 1], // 0x011
     wait_time_cfg_commands: WaitTimeConfigurationCommands,
@@ -134,12 +153,12 @@ pub struct ConfigurationBlock {
     _reserved4: [u8; 4], // 0x03C
     controller_misc_options: u32,
     pub(crate) device_type: u8,
-    serial_flash_pad_type: u8,
+    serial_flash_pad_type: FlashPadType,
     serial_clk_freq: SerialClockFrequency,
     lut_custom_seq_enable: u8,
     _reserved5: [u8; 8], // 0x048
     /// A1, A2, B1, B2
-    serial_flash_sizes: [u32; 4],
+    pub(crate) serial_flash_sizes: [u32; 4],
     cs_pad_setting_override: u32,
     sclk_pad_setting_override: u32,
     data_pad_setting_override: u32,
@@ -147,9 +166,9 @@ pub struct ConfigurationBlock {
     timeout_ms: u32,
     command_interval: u32,
     data_valid_time: u32,
-    busy_offset: u16,
+    pub(crate) busy_offset: u16,
     busy_bit_polarity: u16,
-    lookup_table: LookupTable,
+    pub(crate) lookup_table: [Sequence; lookup::NUMBER_OF_SEQUENCES],
     lut_custom_seq: [u8; 48],
     _reserved6: [u8; 16],
 }
@@ -161,10 +180,10 @@ impl ConfigurationBlock {
         ConfigurationBlock {
             tag: TAG,
             version: VERSION,
-            read_sample_clk_src: ReadSampleClockSource::InternalLoopback as u8,
+            read_sample_clk_src: ReadSampleClockSource::InternalLoopback,
             cs_hold_time: RECOMMENDED_CS_HOLD_TIME,
             cs_setup_time: RECOMMENDED_CS_SETUP_TIME,
-            column_address_width: ColumnAddressWidth::OtherDevices as u8,
+            column_address_width: ColumnAddressWidth::OtherDevices,
             device_mode_configuration: 0, // Disabled
             wait_time_cfg_commands: WaitTimeConfigurationCommands::disable(),
             device_mode_sequence: DeviceModeSequence::new(0, 0),
@@ -174,7 +193,7 @@ impl ConfigurationBlock {
             cfg_cmd_args: [0; 12],
             controller_misc_options: 0,
             device_type: 0, // Invalid value; must be updated in NOR / NAND configuration block
-            serial_flash_pad_type: 1, // Single pad
+            serial_flash_pad_type: FlashPadType::Single,
             serial_clk_freq: SerialClockFrequency::MHz30, // 30MHz
             lut_custom_seq_enable: 0,
             serial_flash_sizes: [0; 4],
@@ -187,7 +206,7 @@ impl ConfigurationBlock {
             data_valid_time: 0,
             busy_offset: 0,
             busy_bit_polarity: 0,
-            lookup_table,
+            lookup_table: lookup_table.sequences,
             lut_custom_seq: [0; 48],
 
             _reserved0: [0; 4],
@@ -204,7 +223,7 @@ impl ConfigurationBlock {
     ///
     /// If not set, this defaults to `ReadSampleClockSource::InternalLoopback`.
     pub const fn read_sample_clk_src(mut self, read_sample_clk_src: ReadSampleClockSource) -> Self {
-        self.read_sample_clk_src = read_sample_clk_src as u8;
+        self.read_sample_clk_src = read_sample_clk_src;
         self
     }
 
@@ -228,7 +247,7 @@ impl ConfigurationBlock {
     ///
     /// If not set, this defaults to `ColumnAddressWidth::OtherDevices`
     pub const fn column_address_width(mut self, column_address_width: ColumnAddressWidth) -> Self {
-        self.column_address_width = column_address_width as u8;
+        self.column_address_width = column_address_width;
         self
     }
 
@@ -258,6 +277,57 @@ impl ConfigurationBlock {
         self
     }
 
+    /// Sets the configuration commands, `configCmdSeqs` and `cfgCmdArgs`
+    ///
+    /// The ROM executes up to three configuration commands, in order. An empty
+    /// slice sets `configCmdEnable` to "disabled." Otherwise, we set `configCmdEnable`
+    /// to "enabled."
+    ///
+    /// If you provide more than three commands, or a command without any LUT sequences,
+    /// you'll observe a compile-time error.
+    ///
+    /// If not set, configuration commands are disabled.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::*;
+    ///
+    /// # const LUT: LookupTable = LookupTable::new();
+    /// const WRITE_STATUS_QUAD_ENABLE: ConfigurationCommand =
+    ///     ConfigurationCommand::new(DeviceModeSequence::new(1, 2), 0x0000_0040);
+    ///
+    /// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock = ConfigurationBlock::new(LUT)
+    ///     .configuration_commands(&[WRITE_STATUS_QUAD_ENABLE]);
+    /// ```
+    pub const fn configuration_commands(mut self, commands: &[ConfigurationCommand]) -> Self {
+        if commands.len() > MAX_CONFIGURATION_COMMANDS {
+            panic!("Too many configuration commands; the maximum is three");
+        }
+        self.config_cmd_seqs = [0; 12];
+        self.cfg_cmd_args = [0; 12];
+        let mut idx = 0;
+        while idx < commands.len() {
+            let command = commands[idx];
+            if command.seq.0[0] == 0 {
+                panic!("A configuration command must have at least one LUT sequence");
+            }
+            self.config_cmd_seqs = write_array(self.config_cmd_seqs, idx * 4, &command.seq.0);
+            self.cfg_cmd_args = write_u32(self.cfg_cmd_args, idx * 4, command.arg);
+            idx += 1;
+        }
+        self.config_cmd_enable = !commands.is_empty() as u8;
+        self
+    }
+
+    /// Sets the custom LUT sequences, `lutCustomSeq`, and sets `lutCustomSeqEnable`
+    /// to "enabled."
+    ///
+    /// If not set, custom LUT sequences are disabled.
+    pub const fn lut_custom_seq(mut self, lut_custom_seq: LutCustomSequence) -> Self {
+        self.lut_custom_seq_enable = 1;
+        self.lut_custom_seq = lut_custom_seq.0;
+        self
+    }
+
     /// Sets `waitTimeCfgCommands`
     ///
     /// If not set, this defaults to `WaitTimeConfigurationCommands::disable()`.
@@ -269,11 +339,63 @@ impl ConfigurationBlock {
         self
     }
 
+    /// Sets the `controllerMiscOption` bit flags
+    ///
+    /// This replaces all flags, including the `ControllerMiscOptions::PAD_SETTING_OVERRIDE`
+    /// flag set by the pad setting overrides. Set the pad setting overrides after this
+    /// call to keep them enabled.
+    ///
+    /// If not set, this defaults to `ControllerMiscOptions::empty()`.
+    pub const fn controller_misc_options(
+        mut self,
+        controller_misc_options: ControllerMiscOptions,
+    ) -> Self {
+        self.controller_misc_options = controller_misc_options.bits();
+        self
+    }
+
+    /// Override the pad setting of the chip select pins, `csPadSettingOverride`
+    ///
+    /// This also sets `ControllerMiscOptions::PAD_SETTING_OVERRIDE`.
+    pub const fn cs_pad_setting_override(mut self, pad_setting: PadSetting) -> Self {
+        self.cs_pad_setting_override = pad_setting.bits();
+        self.pad_setting_override()
+    }
+
+    /// Override the pad setting of the serial clock pins, `sclkPadSettingOverride`
+    ///
+    /// This also sets `ControllerMiscOptions::PAD_SETTING_OVERRIDE`.
+    pub const fn sclk_pad_setting_override(mut self, pad_setting: PadSetting) -> Self {
+        self.sclk_pad_setting_override = pad_setting.bits();
+        self.pad_setting_override()
+    }
+
+    /// Override the pad setting of the data pins, `dataPadSettingOverride`
+    ///
+    /// This also sets `ControllerMiscOptions::PAD_SETTING_OVERRIDE`.
+    pub const fn data_pad_setting_override(mut self, pad_setting: PadSetting) -> Self {
+        self.data_pad_setting_override = pad_setting.bits();
+        self.pad_setting_override()
+    }
+
+    /// Override the pad setting of the DQS pin, `dqsPadSettingOverride`
+    ///
+    /// This also sets `ControllerMiscOptions::PAD_SETTING_OVERRIDE`.
+    pub const fn dqs_pad_setting_override(mut self, pad_setting: PadSetting) -> Self {
+        self.dqs_pad_setting_override = pad_setting.bits();
+        self.pad_setting_override()
+    }
+
+    const fn pad_setting_override(mut self) -> Self {
+        self.controller_misc_options |= ControllerMiscOptions::PAD_SETTING_OVERRIDE.bits();
+        self
+    }
+
     /// Sets the serial flash pad type, `sFlashPad`.
     ///
     /// If not set, this defaults to `FlashPadType::Single`.
     pub const fn serial_flash_pad_type(mut self, serial_flash_pad_type: FlashPadType) -> Self {
-        self.serial_flash_pad_type = serial_flash_pad_type as u8;
+        self.serial_flash_pad_type = serial_flash_pad_type;
         self
     }
 
@@ -285,6 +407,52 @@ impl ConfigurationBlock {
         self
     }
 
+    /// Sets the timeout for all operations, in milliseconds (`timeoutInMs`)
+    ///
+    /// If not set, this defaults to `0`.
+    pub const fn timeout_ms(mut self, timeout_ms: u32) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Sets the interval between commands, in nanoseconds (`commandInterval`)
+    ///
+    /// If not set, this defaults to `0`.
+    pub const fn command_interval(mut self, command_interval_ns: u32) -> Self {
+        self.command_interval = command_interval_ns;
+        self
+    }
+
+    /// Sets the data valid time for the FlexSPI DLL (`dataValidTime`)
+    ///
+    /// If not set, the data valid time is `0` for both ports.
+    pub const fn data_valid_time(mut self, data_valid_time: DataValidTime) -> Self {
+        self.data_valid_time = data_valid_time.raw();
+        self
+    }
+
+    /// Sets the bit offset of the busy bit in the status value (`busyOffset`)
+    ///
+    /// The ROM reads the busy bit from a 32 bit status word. If `busy_offset` is
+    /// outside of that word, you'll observe a compile-time error.
+    ///
+    /// If not set, this defaults to `0`.
+    pub const fn busy_offset(mut self, busy_offset: u16) -> Self {
+        if busy_offset > MAX_BUSY_OFFSET {
+            panic!("The busy offset must be within the 32 bit status word");
+        }
+        self.busy_offset = busy_offset;
+        self
+    }
+
+    /// Sets the polarity of the busy bit (`busyBitPolarity`)
+    ///
+    /// If not set, this defaults to `BusyBitPolarity::BusyWhenSet`.
+    pub const fn busy_bit_polarity(mut self, busy_bit_polarity: BusyBitPolarity) -> Self {
+        self.busy_bit_polarity = busy_bit_polarity as u16;
+        self
+    }
+
     /// Set a flash size for the provided flash region
     ///
     /// Any region that's not set will default to `0`.
@@ -292,7 +460,690 @@ impl ConfigurationBlock {
         self.serial_flash_sizes[flash_region as usize] = flash_size;
         self
     }
+
+    /// Returns the lookup table
+    pub const fn get_lookup_table(&self) -> LookupTable {
+        LookupTable::from_sequences(self.lookup_table)
+    }
+
+    /// Returns the read sample clock source (`readSampleClkSrc`)
+    pub const fn get_read_sample_clk_src(&self) -> ReadSampleClockSource {
+        self.read_sample_clk_src
+    }
+
+    /// Returns the chip select hold time (`csHoldTime`)
+    pub const fn get_cs_hold_time(&self) -> u8 {
+        self.cs_hold_time
+    }
+
+    /// Returns the chip select setup time (`csSetupTime`)
+    pub const fn get_cs_setup_time(&self) -> u8 {
+        self.cs_setup_time
+    }
+
+    /// Returns the column address width (`columnAdressWidth`)
+    pub const fn get_column_address_width(&self) -> ColumnAddressWidth {
+        self.column_address_width
+    }
+
+    /// Returns the device mode configuration
+    ///
+    /// The sequence and argument are only returned if `deviceModeCfgEnable` is
+    /// "enabled."
+    pub const fn get_device_mode_configuration(&self) -> DeviceModeConfiguration {
+        if self.device_mode_configuration == 0 {
+            DeviceModeConfiguration::Disabled
+        } else {
+            DeviceModeConfiguration::Enabled {
+                device_mode_arg: self.device_mode_arg,
+                device_mode_seq: self.device_mode_sequence,
+            }
+        }
+    }
+
+    /// Returns the configuration commands
+    ///
+    /// If `configCmdEnable` is "disabled," all commands are `None`. Otherwise, any
+    /// command without LUT sequences is `None`.
+    pub const fn get_configuration_commands(
+        &self,
+    ) -> [Option<ConfigurationCommand>; MAX_CONFIGURATION_COMMANDS] {
+        let mut commands = [None; MAX_CONFIGURATION_COMMANDS];
+        if self.config_cmd_enable == 0 {
+            return commands;
+        }
+        let mut idx = 0;
+        while idx < MAX_CONFIGURATION_COMMANDS {
+            let seq = DeviceModeSequence(read_array(&self.config_cmd_seqs, idx * 4));
+            if seq.number_of_luts() != 0 {
+                let arg = read_u32(&self.cfg_cmd_args, idx * 4);
+                commands[idx] = Some(ConfigurationCommand::new(seq, arg));
+            }
+            idx += 1;
+        }
+        commands
+    }
+
+    /// Returns the custom LUT sequences (`lutCustomSeq`)
+    ///
+    /// Returns `None` if `lutCustomSeqEnable` is "disabled."
+    pub const fn get_lut_custom_seq(&self) -> Option<LutCustomSequence> {
+        if self.lut_custom_seq_enable == 0 {
+            None
+        } else {
+            Some(LutCustomSequence(self.lut_custom_seq))
+        }
+    }
+
+    /// Returns `waitTimeCfgCommands`
+    pub const fn get_wait_time_cfg_commands(&self) -> WaitTimeConfigurationCommands {
+        self.wait_time_cfg_commands
+    }
+
+    /// Returns the controller misc options (`controllerMiscOption`)
+    pub const fn get_controller_misc_options(&self) -> ControllerMiscOptions {
+        ControllerMiscOptions::from_bits(self.controller_misc_options)
+    }
+
+    /// Returns the device type (`deviceType`)
+    ///
+    /// `1` is serial NOR, and `2` is serial NAND. The device type is `0` until the
+    /// FlexSPI configuration block is used in a serial NOR or NAND configuration block.
+    pub const fn get_device_type(&self) -> u8 {
+        self.device_type
+    }
+
+    /// Returns the chip select pad setting override (`csPadSettingOverride`)
+    ///
+    /// Returns `None` if pad setting overrides are not enabled.
+    pub const fn get_cs_pad_setting_override(&self) -> Option<PadSetting> {
+        self.get_pad_setting_override(self.cs_pad_setting_override)
+    }
+
+    /// Returns the serial clock pad setting override (`sclkPadSettingOverride`)
+    ///
+    /// Returns `None` if pad setting overrides are not enabled.
+    pub const fn get_sclk_pad_setting_override(&self) -> Option<PadSetting> {
+        self.get_pad_setting_override(self.sclk_pad_setting_override)
+    }
+
+    /// Returns the data pad setting override (`dataPadSettingOverride`)
+    ///
+    /// Returns `None` if pad setting overrides are not enabled.
+    pub const fn get_data_pad_setting_override(&self) -> Option<PadSetting> {
+        self.get_pad_setting_override(self.data_pad_setting_override)
+    }
+
+    /// Returns the DQS pad setting override (`dqsPadSettingOverride`)
+    ///
+    /// Returns `None` if pad setting overrides are not enabled.
+    pub const fn get_dqs_pad_setting_override(&self) -> Option<PadSetting> {
+        self.get_pad_setting_override(self.dqs_pad_setting_override)
+    }
+
+    const fn get_pad_setting_override(&self, bits: u32) -> Option<PadSetting> {
+        let options = self.get_controller_misc_options();
+        if options.contains(ControllerMiscOptions::PAD_SETTING_OVERRIDE) {
+            Some(PadSetting::from_bits(bits))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the serial flash pad type (`sFlashPad`)
+    pub const fn get_serial_flash_pad_type(&self) -> FlashPadType {
+        self.serial_flash_pad_type
+    }
+
+    /// Returns the serial clock frequency (`serialClkFreq`)
+    pub const fn get_serial_clk_freq(&self) -> SerialClockFrequency {
+        self.serial_clk_freq
+    }
+
+    /// Returns the timeout, in milliseconds (`timeoutInMs`)
+    pub const fn get_timeout_ms(&self) -> u32 {
+        self.timeout_ms
+    }
+
+    /// Returns the command interval, in nanoseconds (`commandInterval`)
+    pub const fn get_command_interval(&self) -> u32 {
+        self.command_interval
+    }
+
+    /// Returns the data valid time (`dataValidTime`)
+    pub const fn get_data_valid_time(&self) -> DataValidTime {
+        DataValidTime::from_raw(self.data_valid_time)
+    }
+
+    /// Returns the bit offset of the busy bit (`busyOffset`)
+    pub const fn get_busy_offset(&self) -> u16 {
+        self.busy_offset
+    }
+
+    /// Returns the polarity of the busy bit (`busyBitPolarity`)
+    pub const fn get_busy_bit_polarity(&self) -> BusyBitPolarity {
+        BusyBitPolarity::from_raw(self.busy_bit_polarity)
+    }
+
+    /// Returns the flash size for the provided flash region
+    pub const fn get_flash_size(&self, flash_region: SerialFlashRegion) -> u32 {
+        self.serial_flash_sizes[flash_region as usize]
+    }
+
+    /// Check that the lookup table agrees with the rest of the configuration block
+    ///
+    /// For every instruction that may execute, `check_lookup_table` requires that
+    ///
+    /// - the instruction uses no more pads than the [`FlashPadType`].
+    /// - a DDR opcode is only used with [`ControllerMiscOptions::DDR_MODE`].
+    /// - a `DUMMY_RWDS` opcode is only used with [`ReadSampleClockSource::FlashProvidedDQS`].
+    /// - a `CADDR` opcode is only used with [`ColumnAddressWidth::Hyperflash`].
+    ///
+    /// Returns the first error. Use [`lookup_table_errors`](ConfigurationBlock::lookup_table_errors)
+    /// to find all errors at runtime, or [`validate_lookup_table`](ConfigurationBlock::validate_lookup_table)
+    /// to check the lookup table in a `const` context.
+    pub const fn check_lookup_table(&self) -> Result<(), LutError> {
+        match check::next_error(self, 0) {
+            Some((err, _)) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns an iterator over all errors described in
+    /// [`check_lookup_table`](ConfigurationBlock::check_lookup_table)
+    pub const fn lookup_table_errors(&self) -> LutErrors<'_> {
+        LutErrors::new(self)
+    }
+
+    /// Validate the lookup table against the rest of the configuration block
+    ///
+    /// If the lookup table has any of the errors described in
+    /// [`check_lookup_table`](ConfigurationBlock::check_lookup_table), you'll observe
+    /// a compile-time error.
+    pub const fn validate_lookup_table(self) -> Self {
+        match self.check_lookup_table() {
+            Ok(()) => self,
+            Err(err) => panic!("{}", err.kind().message()),
+        }
+    }
+
+    /// Returns the memory representation of the FlexSPI configuration block
+    ///
+    /// Multi-byte fields are always little endian, regardless of the host's
+    /// endianness.
+    pub const fn to_bytes(&self) -> [u8; 448] {
+        self.encode([0; 448])
+    }
+
+    /// Encode the FlexSPI configuration block into the start of `bytes`
+    ///
+    /// `bytes` must have at least 448 bytes.
+    pub(crate) const fn encode<const N: usize>(&self, bytes: [u8; N]) -> [u8; N] {
+        let mut bytes = write_u32(bytes, 0x000, self.tag);
+        bytes = write_u32(bytes, 0x004, self.version);
+        bytes = write_array(bytes, 0x008, &self._reserved0);
+        bytes[0x00C] = self.read_sample_clk_src as u8;
+        bytes[0x00D] = self.cs_hold_time;
+        bytes[0x00E] = self.cs_setup_time;
+        bytes[0x00F] = self.column_address_width as u8;
+        bytes[0x010] = self.device_mode_configuration;
+        bytes = write_array(bytes, 0x011, &self._reserved1);
+        bytes = write_u16(bytes, 0x012, self.wait_time_cfg_commands.0);
+        bytes = write_array(bytes, 0x014, &self.device_mode_sequence.0);
+        bytes = write_u32(bytes, 0x018, self.device_mode_arg);
+        bytes[0x01C] = self.config_cmd_enable;
+        bytes = write_array(bytes, 0x01D, &self._reserved2);
+        bytes = write_array(bytes, 0x020, &self.config_cmd_seqs);
+        bytes = write_array(bytes, 0x02C, &self._reserved3);
+        bytes = write_array(bytes, 0x030, &self.cfg_cmd_args);
+        bytes = write_array(bytes, 0x03C, &self._reserved4);
+        bytes = write_u32(bytes, 0x040, self.controller_misc_options);
+        bytes[0x044] = self.device_type;
+        bytes[0x045] = self.serial_flash_pad_type as u8;
+        bytes[0x046] = self.serial_clk_freq as u8;
+        bytes[0x047] = self.lut_custom_seq_enable;
+        bytes = write_array(bytes, 0x048, &self._reserved5);
+        let serial_flash_sizes = self.serial_flash_sizes;
+        bytes = write_u32(bytes, 0x050, serial_flash_sizes[0]);
+        bytes = write_u32(bytes, 0x054, serial_flash_sizes[1]);
+        bytes = write_u32(bytes, 0x058, serial_flash_sizes[2]);
+        bytes = write_u32(bytes, 0x05C, serial_flash_sizes[3]);
+        bytes = write_u32(bytes, 0x060, self.cs_pad_setting_override);
+        bytes = write_u32(bytes, 0x064, self.sclk_pad_setting_override);
+        bytes = write_u32(bytes, 0x068, self.data_pad_setting_override);
+        bytes = write_u32(bytes, 0x06C, self.dqs_pad_setting_override);
+        bytes = write_u32(bytes, 0x070, self.timeout_ms);
+        bytes = write_u32(bytes, 0x074, self.command_interval);
+        bytes = write_u32(bytes, 0x078, self.data_valid_time);
+        bytes = write_u16(bytes, 0x07C, self.busy_offset);
+        bytes = write_u16(bytes, 0x07E, self.busy_bit_polarity);
+        bytes = self.get_lookup_table().encode(bytes, 0x080);
+        bytes = write_array(bytes, 0x180, &self.lut_custom_seq);
+        write_array(bytes, 0x1B0, &self._reserved6)
+    }
+
+    /// Decode a FlexSPI configuration block from its memory representation
+    ///
+    /// `from_bytes` checks the tag and version, and rejects any field that doesn't
+    /// have a valid value. Use this to inspect a configuration block that was read
+    /// from a device.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{ConfigurationBlock, DecodeError};
+    ///
+    /// let bytes = [0; 448];
+    /// assert_eq!(ConfigurationBlock::from_bytes(&bytes), Err(DecodeError::Tag(0)));
+    /// ```
+    pub const fn from_bytes(bytes: &[u8; 448]) -> Result<Self, DecodeError> {
+        ConfigurationBlock::decode(bytes)
+    }
+
+    /// Decode a FlexSPI configuration block from the start of `bytes`
+    ///
+    /// `bytes` must have at least 448 bytes.
+    pub(crate) const fn decode(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let tag = read_u32(bytes, 0x000);
+        if tag != TAG {
+            return Err(DecodeError::Tag(tag));
+        }
+        let version = read_u32(bytes, 0x004);
+        if version & VERSION_MASK != VERSION & VERSION_MASK {
+            return Err(DecodeError::Version(version));
+        }
+        let read_sample_clk_src = match ReadSampleClockSource::from_raw(bytes[0x00C]) {
+            Some(read_sample_clk_src) => read_sample_clk_src,
+            None => return Err(DecodeError::ReadSampleClockSource(bytes[0x00C])),
+        };
+        let column_address_width = match ColumnAddressWidth::from_raw(bytes[0x00F]) {
+            Some(column_address_width) => column_address_width,
+            None => return Err(DecodeError::ColumnAddressWidth(bytes[0x00F])),
+        };
+        let device_mode_configuration = match bytes[0x010] {
+            0 | 1 => bytes[0x010],
+            unknown => return Err(DecodeError::DeviceModeConfiguration(unknown)),
+        };
+        let serial_flash_pad_type = match FlashPadType::from_raw(bytes[0x045]) {
+            Some(serial_flash_pad_type) => serial_flash_pad_type,
+            None => return Err(DecodeError::FlashPadType(bytes[0x045])),
+        };
+        let serial_clk_freq = match SerialClockFrequency::from_raw(bytes[0x046]) {
+            Some(serial_clk_freq) => serial_clk_freq,
+            None => return Err(DecodeError::SerialClockFrequency(bytes[0x046])),
+        };
+
+        Ok(ConfigurationBlock {
+            tag,
+            version,
+            _reserved0: read_array(bytes, 0x008),
+            read_sample_clk_src,
+            cs_hold_time: bytes[0x00D],
+            cs_setup_time: bytes[0x00E],
+            column_address_width,
+            device_mode_configuration,
+            _reserved1: read_array(bytes, 0x011),
+            wait_time_cfg_commands: WaitTimeConfigurationCommands(read_u16(bytes, 0x012)),
+            device_mode_sequence: DeviceModeSequence(read_array(bytes, 0x014)),
+            device_mode_arg: read_u32(bytes, 0x018),
+            config_cmd_enable: bytes[0x01C],
+            _reserved2: read_array(bytes, 0x01D),
+            config_cmd_seqs: read_array(bytes, 0x020),
+            _reserved3: read_array(bytes, 0x02C),
+            cfg_cmd_args: read_array(bytes, 0x030),
+            _reserved4: read_array(bytes, 0x03C),
+            controller_misc_options: read_u32(bytes, 0x040),
+            device_type: bytes[0x044],
+            serial_flash_pad_type,
+            serial_clk_freq,
+            lut_custom_seq_enable: bytes[0x047],
+            _reserved5: read_array(bytes, 0x048),
+            serial_flash_sizes: [
+                read_u32(bytes, 0x050),
+                read_u32(bytes, 0x054),
+                read_u32(bytes, 0x058),
+                read_u32(bytes, 0x05C),
+            ],
+            cs_pad_setting_override: read_u32(bytes, 0x060),
+            sclk_pad_setting_override: read_u32(bytes, 0x064),
+            data_pad_setting_override: read_u32(bytes, 0x068),
+            dqs_pad_setting_override: read_u32(bytes, 0x06C),
+            timeout_ms: read_u32(bytes, 0x070),
+            command_interval: read_u32(bytes, 0x074),
+            data_valid_time: read_u32(bytes, 0x078),
+            busy_offset: read_u16(bytes, 0x07C),
+            busy_bit_polarity: read_u16(bytes, 0x07E),
+            lookup_table: LookupTable::decode(bytes, 0x080).sequences,
+            lut_custom_seq: read_array(bytes, 0x180),
+            _reserved6: read_array(bytes, 0x1B0),
+        })
+    }
+}
+
+/// An error that occurs when decoding a configuration block
+///
+/// Each variant carries the unexpected value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The tag is not ASCII 'FCFB'
+    Tag(u32),
+    /// The version is not a supported version
+    Version(u32),
+    /// Unknown `readSampleClkSrc`
+    ReadSampleClockSource(u8),
+    /// Unknown `columnAddressWidth`
+    ColumnAddressWidth(u8),
+    /// Unknown `deviceModeCfgEnable`
+    DeviceModeConfiguration(u8),
+    /// Unknown `sFlashPad`
+    FlashPadType(u8),
+    /// Unknown `serialClkFreq`
+    SerialClockFrequency(u8),
+    /// The `deviceType` does not match the configuration block
+    DeviceType(u8),
+    /// Unknown `ipCmdSerialClkFreq`
+    IpCmdSerialClockFrequency(u32),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::Tag(tag) => write!(f, "invalid tag {:#010X}", tag),
+            DecodeError::Version(version) => write!(f, "unsupported version {:#010X}", version),
+            DecodeError::ReadSampleClockSource(raw) => {
+                write!(f, "unknown readSampleClkSrc {:#04X}", raw)
+            }
+            DecodeError::ColumnAddressWidth(raw) => {
+                write!(f, "unknown columnAddressWidth {:#04X}", raw)
+            }
+            DecodeError::DeviceModeConfiguration(raw) => {
+                write!(f, "unknown deviceModeCfgEnable {:#04X}", raw)
+            }
+            DecodeError::FlashPadType(raw) => write!(f, "unknown sFlashPad {:#04X}", raw),
+            DecodeError::SerialClockFrequency(raw) => {
+                write!(f, "unknown serialClkFreq {:#04X}", raw)
+            }
+            DecodeError::DeviceType(raw) => write!(f, "unexpected deviceType {:#04X}", raw),
+            DecodeError::IpCmdSerialClockFrequency(raw) => {
+                write!(f, "unknown ipCmdSerialClkFreq {:#010X}", raw)
+            }
+        }
+    }
 }
 
 const _STATIC_ASSERT_SIZE: [u32; 1] =
     [0; (core::mem::size_of::<ConfigurationBlock>() == 448) as usize];
+
+#[cfg(test)]
+mod test {
+    use super::{
+        BusyBitPolarity, ConfigurationBlock, ConfigurationCommand, ControllerMiscOptions,
+        DataValidTime, DecodeError, DeviceModeConfiguration, DeviceModeSequence, LookupTable,
+        LutCustomSequence, Operation, PadSetting, ReadSampleClockSource, SerialFlashRegion,
+        WaitTimeConfigurationCommands,
+    };
+
+    const CFG: ConfigurationBlock = ConfigurationBlock::new(LookupTable::new());
+
+    #[test]
+    fn configuration_commands() {
+        const BYTES: [u8; 448] = CFG
+            .configuration_commands(&[
+                ConfigurationCommand::new(DeviceModeSequence::new(1, 2), 0x0000_0040),
+                ConfigurationCommand::new(DeviceModeSequence::new(2, 6), 0x1234_5678),
+            ])
+            .to_bytes();
+        assert_eq!(BYTES[0x01C], 1);
+        assert_eq!(
+            BYTES[0x020..0x02C],
+            [0x01, 0x02, 0, 0, 0x02, 0x06, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            BYTES[0x030..0x03C],
+            [0x40, 0, 0, 0, 0x78, 0x56, 0x34, 0x12, 0, 0, 0, 0]
+        );
+
+        const DISABLED: [u8; 448] = CFG.configuration_commands(&[]).to_bytes();
+        assert_eq!(DISABLED[0x01C], 0);
+    }
+
+    #[test]
+    fn controller_misc_options() {
+        const BYTES: [u8; 448] = CFG
+            .controller_misc_options(
+                ControllerMiscOptions::SAFE_CONFIG_FREQ.union(ControllerMiscOptions::DDR_MODE),
+            )
+            .to_bytes();
+        assert_eq!(BYTES[0x040..0x044], [0x50, 0, 0, 0]);
+    }
+
+    #[test]
+    fn pad_setting_override() {
+        const PAD_SETTING: PadSetting = PadSetting::new().open_drain(true);
+        const BYTES: [u8; 448] = CFG
+            .controller_misc_options(ControllerMiscOptions::DDR_MODE)
+            .data_pad_setting_override(PAD_SETTING)
+            .to_bytes();
+        assert_eq!(BYTES[0x040..0x044], [0x60, 0, 0, 0]);
+        assert_eq!(BYTES[0x060..0x064], [0; 4]);
+        assert_eq!(BYTES[0x068..0x06C], PAD_SETTING.bits().to_le_bytes());
+
+        // The misc options are exactly what the caller provides.
+        const REPLACED: [u8; 448] = CFG
+            .data_pad_setting_override(PAD_SETTING)
+            .controller_misc_options(ControllerMiscOptions::DDR_MODE)
+            .to_bytes();
+        assert_eq!(REPLACED[0x040..0x044], [0x40, 0, 0, 0]);
+    }
+
+    #[test]
+    fn timing_and_busy_bit() {
+        const BYTES: [u8; 448] = CFG
+            .timeout_ms(500)
+            .command_interval(100)
+            .data_valid_time(DataValidTime::from_tenths_ns(16, 20))
+            .busy_offset(7)
+            .busy_bit_polarity(BusyBitPolarity::BusyWhenClear)
+            .to_bytes();
+        assert_eq!(BYTES[0x070..0x074], 500u32.to_le_bytes());
+        assert_eq!(BYTES[0x074..0x078], 100u32.to_le_bytes());
+        assert_eq!(BYTES[0x078..0x07C], [16, 0, 20, 0]);
+        assert_eq!(BYTES[0x07C..0x080], [7, 0, 1, 0]);
+        assert_eq!(
+            DataValidTime::new(2, 3),
+            DataValidTime::from_tenths_ns(20, 30)
+        );
+    }
+
+    #[test]
+    fn lut_custom_seq() {
+        const BYTES: [u8; 448] = CFG
+            .lut_custom_seq(
+                LutCustomSequence::new()
+                    .sequence(Operation::EraseSector, DeviceModeSequence::new(2, 12))
+                    .sequence(Operation::ExitNoCmd, DeviceModeSequence::new(1, 15)),
+            )
+            .to_bytes();
+        assert_eq!(BYTES[0x047], 1);
+        let mut expected = [0; 48];
+        expected[5 * 4..5 * 4 + 2].copy_from_slice(&[2, 12]);
+        expected[11 * 4..11 * 4 + 2].copy_from_slice(&[1, 15]);
+        assert_eq!(BYTES[0x180..0x1B0], expected);
+    }
+
+    #[test]
+    fn getters() {
+        const COMMAND: ConfigurationCommand =
+            ConfigurationCommand::new(DeviceModeSequence::new(2, 6), 0x1234_5678);
+        const DEVICE_MODE: DeviceModeConfiguration = DeviceModeConfiguration::Enabled {
+            device_mode_arg: 0x02,
+            device_mode_seq: DeviceModeSequence::new(1, 12),
+        };
+        const PAD_SETTING: PadSetting = PadSetting::new().open_drain(true);
+        let cfg = CFG
+            .cs_hold_time(0x01)
+            .device_mode_configuration(DEVICE_MODE)
+            .configuration_commands(&[COMMAND])
+            .wait_time_cfg_commands(WaitTimeConfigurationCommands::new(500))
+            .sclk_pad_setting_override(PAD_SETTING)
+            .data_valid_time(DataValidTime::from_tenths_ns(16, 20))
+            .busy_bit_polarity(BusyBitPolarity::BusyWhenClear)
+            .flash_size(SerialFlashRegion::B1, 0x0100_0000);
+
+        assert_eq!(cfg.get_cs_hold_time(), 0x01);
+        assert_eq!(cfg.get_device_mode_configuration(), DEVICE_MODE);
+        assert_eq!(
+            cfg.get_configuration_commands(),
+            [Some(COMMAND), None, None]
+        );
+        assert_eq!(cfg.get_wait_time_cfg_commands().wait_time_us(), 500);
+        assert_eq!(cfg.get_sclk_pad_setting_override(), Some(PAD_SETTING));
+        assert_eq!(cfg.get_cs_pad_setting_override(), Some(PadSetting::new()));
+        assert_eq!(cfg.get_data_valid_time().port_b(), 20);
+        assert_eq!(cfg.get_busy_bit_polarity(), BusyBitPolarity::BusyWhenClear);
+        assert_eq!(cfg.get_flash_size(SerialFlashRegion::B1), 0x0100_0000);
+        assert_eq!(cfg.get_flash_size(SerialFlashRegion::A1), 0);
+        assert_eq!(cfg.get_lut_custom_seq(), None);
+
+        assert_eq!(CFG.get_configuration_commands(), [None; 3]);
+        assert_eq!(CFG.get_cs_pad_setting_override(), None);
+        assert_eq!(
+            CFG.get_device_mode_configuration(),
+            DeviceModeConfiguration::Disabled
+        );
+    }
+
+    #[test]
+    fn decode() {
+        let cfg = CFG.read_sample_clk_src(ReadSampleClockSource::FlashProvidedDQS);
+        assert_eq!(ConfigurationBlock::from_bytes(&cfg.to_bytes()), Ok(cfg));
+    }
+
+    #[test]
+    fn decode_minor_version() {
+        let mut bytes = CFG.to_bytes();
+        bytes[0x005] = 0x04;
+        let cfg = ConfigurationBlock::from_bytes(&bytes).unwrap();
+        assert_eq!({ cfg.version }, 0x5601_0400);
+    }
+
+    #[test]
+    fn decode_errors() {
+        let mut bytes = CFG.to_bytes();
+        bytes[0x007] = 0x57;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&bytes),
+            Err(DecodeError::Version(0x5701_0000))
+        );
+
+        let mut bytes = CFG.to_bytes();
+        bytes[0x00C] = 0x02;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&bytes),
+            Err(DecodeError::ReadSampleClockSource(0x02))
+        );
+
+        let mut bytes = CFG.to_bytes();
+        bytes[0x045] = 0x03;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&bytes),
+            Err(DecodeError::FlashPadType(0x03))
+        );
+
+        let mut bytes = CFG.to_bytes();
+        bytes[0x046] = 0xFF;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&bytes),
+            Err(DecodeError::SerialClockFrequency(0xFF))
+        );
+    }
+}
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::*;
+/// const CMD: ConfigurationCommand = ConfigurationCommand::new(DeviceModeSequence::new(1, 2), 0);
+/// const CFG: ConfigurationBlock = ConfigurationBlock::new(LookupTable::new())
+///     .configuration_commands(&[CMD, CMD, CMD]);
+/// ```
+#[cfg(doctest)]
+struct ConfigurationCommandsLimit;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::*;
+/// const CMD: ConfigurationCommand = ConfigurationCommand::new(DeviceModeSequence::new(1, 2), 0);
+/// const CFG: ConfigurationBlock = ConfigurationBlock::new(LookupTable::new())
+///     .configuration_commands(&[CMD, CMD, CMD, CMD]); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct ConfigurationCommandsTooMany;
+
+/// ```
+/// use imxrt_boot_gen::flexspi::*;
+/// const CFG: ConfigurationBlock = ConfigurationBlock::new(LookupTable::new()).busy_offset(31);
+/// ```
+#[cfg(doctest)]
+struct BusyOffsetLimit;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::*;
+/// const CFG: ConfigurationBlock = ConfigurationBlock::new(LookupTable::new())
+///     .busy_offset(32); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct BusyOffsetTooLarge;
+
+/// ```
+/// use imxrt_boot_gen::flexspi::*;
+/// const LUT_CUSTOM_SEQUENCE: LutCustomSequence = LutCustomSequence::new()
+///     .sequence(Operation::PageProgram, DeviceModeSequence::new(2, 14));
+/// ```
+#[cfg(doctest)]
+struct LutCustomSequenceLimit;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::*;
+/// const LUT_CUSTOM_SEQUENCE: LutCustomSequence = LutCustomSequence::new()
+///     .sequence(Operation::PageProgram, DeviceModeSequence::new(2, 15)); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct LutCustomSequenceOutOfBounds;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::Read, SequenceBuilder::new()
+///         .instr(Instr::new(CMD, Pads::One, 0xEB))
+///         .instr(Instr::new(READ, Pads::Four, 0x04))
+///         .build());
+/// const CFG: ConfigurationBlock = ConfigurationBlock::new(LUT)
+///     .serial_flash_pad_type(FlashPadType::Quad)
+///     .validate_lookup_table();
+/// ```
+#[cfg(doctest)]
+struct LookupTablePadsConsistent;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::Read, SequenceBuilder::new()
+///         .instr(Instr::new(CMD, Pads::One, 0xEB))
+///         .instr(Instr::new(READ, Pads::Four, 0x04))
+///         .build());
+/// const CFG: ConfigurationBlock = ConfigurationBlock::new(LUT)
+///     .serial_flash_pad_type(FlashPadType::Dual) // <------- THIS SHOULD FAIL
+///     .validate_lookup_table();
+/// ```
+#[cfg(doctest)]
+struct LookupTablePadsTooWide;
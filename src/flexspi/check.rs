@@ -0,0 +1,286 @@
+//! Consistency checks between the lookup table and the rest of the
+//! FlexSPI configuration block
+
+use core::fmt;
+
+use super::opcodes::{ddr, sdr};
+use super::sequence::INSTRUCTIONS_PER_SEQUENCE;
+use super::{
+    ColumnAddressWidth, ConfigurationBlock, ControllerMiscOptions, FlashPadType, Instr, Pads,
+    ReadSampleClockSource, JUMP_ON_CS, STOP,
+};
+use crate::flexspi::lookup::NUMBER_OF_SEQUENCES;
+
+/// Describes how a lookup table instruction disagrees with the FlexSPI
+/// configuration block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LutErrorKind {
+    /// The instruction uses more pads than the `sFlashPad` field allows
+    PadsWiderThanFlash {
+        /// The instruction's pads
+        pads: Pads,
+        /// The configuration block's flash pad type
+        flash_pad_type: FlashPadType,
+    },
+    /// The instruction is a DDR opcode, but [`ControllerMiscOptions::DDR_MODE`]
+    /// is not set
+    DdrWithoutDdrMode,
+    /// The instruction is a `DUMMY_RWDS` opcode, but the read sample clock source
+    /// is not [`ReadSampleClockSource::FlashProvidedDQS`]
+    DummyRwdsWithoutDqs,
+    /// The instruction is a `CADDR` opcode, but the column address width
+    /// is not [`ColumnAddressWidth::Hyperflash`]
+    CaddrWithoutHyperflash,
+}
+
+impl LutErrorKind {
+    /// Returns a message that describes the error
+    pub const fn message(self) -> &'static str {
+        match self {
+            LutErrorKind::PadsWiderThanFlash { .. } => {
+                "A LUT instruction uses more pads than the flash pad type"
+            }
+            LutErrorKind::DdrWithoutDdrMode => {
+                "A LUT instruction uses a DDR opcode, but DDR mode is not enabled"
+            }
+            LutErrorKind::DummyRwdsWithoutDqs => {
+                "A LUT instruction uses DUMMY_RWDS, but the read sample clock source is not FlashProvidedDQS"
+            }
+            LutErrorKind::CaddrWithoutHyperflash => {
+                "A LUT instruction uses CADDR, but the column address width is not Hyperflash"
+            }
+        }
+    }
+}
+
+/// An inconsistency between a lookup table instruction and the FlexSPI
+/// configuration block
+///
+/// See [`ConfigurationBlock::check_lookup_table`] for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LutError {
+    sequence: u8,
+    instruction: u8,
+    kind: LutErrorKind,
+}
+
+impl LutError {
+    /// Returns the LUT index of the sequence that holds the instruction
+    pub const fn sequence(&self) -> usize {
+        self.sequence as usize
+    }
+    /// Returns the position of the instruction in its sequence
+    pub const fn instruction(&self) -> usize {
+        self.instruction as usize
+    }
+    /// Returns the kind of error
+    pub const fn kind(&self) -> LutErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for LutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LUT sequence {}, instruction {}: {}",
+            self.sequence,
+            self.instruction,
+            self.kind.message()
+        )?;
+        if let LutErrorKind::PadsWiderThanFlash {
+            pads,
+            flash_pad_type,
+        } = self.kind
+        {
+            write!(f, " ({} pads, {} flash pads)", pads, flash_pad_type as u8)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check a single instruction against the configuration block
+const fn check_instr(cfg: &ConfigurationBlock, instr: Instr) -> Option<LutErrorKind> {
+    let opcode = instr.opcode();
+    let pads = instr.pads();
+    let flash_pad_type = cfg.serial_flash_pad_type;
+    if pads.width() > flash_pad_type as u8 {
+        return Some(LutErrorKind::PadsWiderThanFlash {
+            pads,
+            flash_pad_type,
+        });
+    }
+    let ddr_mode = cfg.controller_misc_options & ControllerMiscOptions::DDR_MODE.bits() != 0;
+    if opcode.is_ddr() && !ddr_mode {
+        return Some(LutErrorKind::DdrWithoutDdrMode);
+    }
+    if (opcode.is(sdr::DUMMY_RWDS) || opcode.is(ddr::DUMMY_RWDS))
+        && !matches!(
+            cfg.read_sample_clk_src,
+            ReadSampleClockSource::FlashProvidedDQS
+        )
+    {
+        return Some(LutErrorKind::DummyRwdsWithoutDqs);
+    }
+    if (opcode.is(sdr::CADDR) || opcode.is(ddr::CADDR))
+        && !matches!(cfg.column_address_width, ColumnAddressWidth::Hyperflash)
+    {
+        return Some(LutErrorKind::CaddrWithoutHyperflash);
+    }
+    None
+}
+
+/// Find the next error, starting at the instruction `position`
+///
+/// `position` counts instructions from the start of the lookup table. Returns the
+/// error, and the position after the error. Instructions that follow a `STOP` are
+/// never executed, so they're not checked.
+pub(crate) const fn next_error(
+    cfg: &ConfigurationBlock,
+    mut position: usize,
+) -> Option<(LutError, usize)> {
+    let lookup_table = cfg.lookup_table;
+    while position < NUMBER_OF_SEQUENCES * INSTRUCTIONS_PER_SEQUENCE {
+        let sequence = position / INSTRUCTIONS_PER_SEQUENCE;
+        let instruction = position % INSTRUCTIONS_PER_SEQUENCE;
+        let instr = lookup_table[sequence].0[instruction];
+        let opcode = instr.opcode();
+        if opcode.is(STOP.opcode()) || opcode.is(JUMP_ON_CS.opcode()) {
+            position = (sequence + 1) * INSTRUCTIONS_PER_SEQUENCE;
+            continue;
+        }
+        if let Some(kind) = check_instr(cfg, instr) {
+            let err = LutError {
+                sequence: sequence as u8,
+                instruction: instruction as u8,
+                kind,
+            };
+            return Some((err, position + 1));
+        }
+        position += 1;
+    }
+    None
+}
+
+/// An iterator over all [`LutError`]s in a configuration block
+///
+/// Use [`ConfigurationBlock::lookup_table_errors`] to create the iterator.
+#[derive(Debug, Clone)]
+pub struct LutErrors<'a> {
+    cfg: &'a ConfigurationBlock,
+    position: usize,
+}
+
+impl<'a> LutErrors<'a> {
+    pub(crate) const fn new(cfg: &'a ConfigurationBlock) -> Self {
+        LutErrors { cfg, position: 0 }
+    }
+}
+
+impl Iterator for LutErrors<'_> {
+    type Item = LutError;
+    fn next(&mut self) -> Option<LutError> {
+        let (err, position) = next_error(self.cfg, self.position)?;
+        self.position = position;
+        Some(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LutError, LutErrorKind};
+    use crate::flexspi::{
+        opcodes::{ddr, sdr},
+        ColumnAddressWidth, Command, ConfigurationBlock, ControllerMiscOptions, FlashPadType,
+        Instr, LookupTable, Pads, ReadSampleClockSource, SequenceBuilder,
+    };
+
+    const LUT: LookupTable = LookupTable::new()
+        .command(
+            Command::Read,
+            SequenceBuilder::new()
+                .instr(Instr::new(sdr::CMD, Pads::One, 0xEB))
+                .instr(Instr::new(sdr::RADDR, Pads::Four, 0x18))
+                .instr(Instr::new(sdr::DUMMY, Pads::Four, 0x06))
+                .instr(Instr::new(sdr::READ, Pads::Four, 0x04))
+                .build(),
+        )
+        .command(
+            Command::ReadStatus,
+            SequenceBuilder::new()
+                .instr(Instr::new(ddr::CMD, Pads::One, 0x05))
+                .instr(Instr::new(sdr::CADDR, Pads::One, 0x10))
+                .instr(Instr::new(sdr::DUMMY_RWDS, Pads::One, 0x04))
+                .build(),
+        );
+
+    #[test]
+    fn consistent() {
+        const CFG: ConfigurationBlock = ConfigurationBlock::new(LUT)
+            .serial_flash_pad_type(FlashPadType::Quad)
+            .controller_misc_options(ControllerMiscOptions::DDR_MODE)
+            .read_sample_clk_src(ReadSampleClockSource::FlashProvidedDQS)
+            .column_address_width(ColumnAddressWidth::Hyperflash)
+            .validate_lookup_table();
+        assert_eq!(CFG.check_lookup_table(), Ok(()));
+        assert_eq!(CFG.lookup_table_errors().count(), 0);
+    }
+
+    #[test]
+    fn report() {
+        const CFG: ConfigurationBlock = ConfigurationBlock::new(LUT);
+        let errors: Vec<LutError> = CFG.lookup_table_errors().collect();
+        let errors: Vec<_> = errors
+            .iter()
+            .map(|err| (err.sequence(), err.instruction(), err.kind()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (
+                    0,
+                    1,
+                    LutErrorKind::PadsWiderThanFlash {
+                        pads: Pads::Four,
+                        flash_pad_type: FlashPadType::Single
+                    }
+                ),
+                (
+                    0,
+                    2,
+                    LutErrorKind::PadsWiderThanFlash {
+                        pads: Pads::Four,
+                        flash_pad_type: FlashPadType::Single
+                    }
+                ),
+                (
+                    0,
+                    3,
+                    LutErrorKind::PadsWiderThanFlash {
+                        pads: Pads::Four,
+                        flash_pad_type: FlashPadType::Single
+                    }
+                ),
+                (1, 0, LutErrorKind::DdrWithoutDdrMode),
+                (1, 1, LutErrorKind::CaddrWithoutHyperflash),
+                (1, 2, LutErrorKind::DummyRwdsWithoutDqs),
+            ]
+        );
+        assert_eq!(
+            CFG.check_lookup_table().map_err(|err| err.kind()),
+            Err(errors[0].2)
+        );
+    }
+
+    #[test]
+    fn display() {
+        const CFG: ConfigurationBlock =
+            ConfigurationBlock::new(LUT).serial_flash_pad_type(FlashPadType::Dual);
+        let err = CFG.check_lookup_table().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "LUT sequence 0, instruction 1: A LUT instruction uses more pads than the flash pad type (QUAD pads, 2 flash pads)"
+        );
+    }
+}
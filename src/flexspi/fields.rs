@@ -1,5 +1,9 @@
 //! FlexSPI configuration block fields
 
+use core::ops::{BitOr, BitOrAssign};
+
+use super::lookup::NUMBER_OF_SEQUENCES;
+
 /// `readSampleClkSrc` of the general FCB   
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -9,6 +13,18 @@ pub enum ReadSampleClockSource {
     FlashProvidedDQS = 0x03,
 }
 
+impl ReadSampleClockSource {
+    pub(crate) const fn from_raw(raw: u8) -> Option<Self> {
+        use ReadSampleClockSource::*;
+        match raw {
+            0x00 => Some(InternalLoopback),
+            0x01 => Some(LoopbackFromDQSPad),
+            0x03 => Some(FlashProvidedDQS),
+            _ => None,
+        }
+    }
+}
+
 /// `columnAdressWidth`
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -17,10 +33,23 @@ pub enum ColumnAddressWidth {
     Hyperflash = 3,
 }
 
+impl ColumnAddressWidth {
+    pub(crate) const fn from_raw(raw: u8) -> Option<Self> {
+        match raw {
+            0 => Some(ColumnAddressWidth::OtherDevices),
+            3 => Some(ColumnAddressWidth::Hyperflash),
+            _ => None,
+        }
+    }
+}
+
 /// Sequence parameter for device mode configuration
+///
+/// A `DeviceModeSequence` also describes the LUT sequences of a
+/// [`ConfigurationCommand`].
 #[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
 #[repr(transparent)]
-pub struct DeviceModeSequence([u8; 4]);
+pub struct DeviceModeSequence(pub(crate) [u8; 4]);
 impl DeviceModeSequence {
     /// Create a new sequence parameter for device configuration
     ///
@@ -31,12 +60,20 @@ impl DeviceModeSequence {
             (((starting_lut_index as u32) << 8) | (number_of_luts as u32)).to_le_bytes(),
         )
     }
+    /// Returns the number of LUT sequences
+    pub const fn number_of_luts(self) -> u8 {
+        self.0[0]
+    }
+    /// Returns the starting LUT index
+    pub const fn starting_lut_index(self) -> u8 {
+        self.0[1]
+    }
 }
 
 /// Describes both the `deviceModeCfgEnable` field, and
 /// the `deviceModeArg` field, which is only valid if
 /// the configuration is enabled.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DeviceModeConfiguration {
     /// Device configuration mode is disabled
     Disabled,
@@ -51,12 +88,121 @@ pub enum DeviceModeConfiguration {
     },
 }
 
+#[allow(clippy::derivable_impls)] // #[default] on enum variants requires Rust 1.62
 impl Default for DeviceModeConfiguration {
     fn default() -> Self {
         DeviceModeConfiguration::Disabled
     }
 }
 
+/// The maximum number of configuration commands
+pub(crate) const MAX_CONFIGURATION_COMMANDS: usize = 3;
+
+/// A configuration command, described by `configCmdSeqs` and `cfgCmdArgs`
+///
+/// The ROM executes configuration commands before it reads from the flash device.
+/// Use configuration commands to set a quad enable bit, or to switch the device into
+/// QPI or OPI mode.
+///
+/// `seq` describes the LUT sequences that implement the command, and `arg` is the
+/// command's argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigurationCommand {
+    pub(crate) seq: DeviceModeSequence,
+    pub(crate) arg: u32,
+}
+
+impl ConfigurationCommand {
+    /// Create a new configuration command
+    pub const fn new(seq: DeviceModeSequence, arg: u32) -> Self {
+        ConfigurationCommand { seq, arg }
+    }
+    /// Returns the LUT sequences that implement the command
+    pub const fn seq(self) -> DeviceModeSequence {
+        self.seq
+    }
+    /// Returns the command's argument
+    pub const fn arg(self) -> u32 {
+        self.arg
+    }
+}
+
+/// A ROM operation that may use custom LUT sequences
+///
+/// The variants are in the order of the `lutCustomSeq` entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(usize)]
+pub enum Operation {
+    Read,
+    ReadStatus,
+    ReadStatusXpi,
+    WriteEnable,
+    WriteEnableXpi,
+    EraseSector,
+    EraseBlock,
+    PageProgram,
+    ChipErase,
+    ReadSfdp,
+    RestoreNoCmd,
+    ExitNoCmd,
+}
+
+/// The number of `lutCustomSeq` entries
+const NUMBER_OF_OPERATIONS: usize = 12;
+
+/// `lutCustomSeq`, the LUT sequences that the ROM uses for each [`Operation`]
+///
+/// By default, the ROM uses one LUT sequence per operation, found at the operation's
+/// [`Command`](crate::flexspi::Command) index. Use a `LutCustomSequence` to remap
+/// an operation to any LUT index, or to span an operation across multiple
+/// consecutive LUT sequences. Any operation that's not set keeps a bit pattern of
+/// zero.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::*;
+///
+/// # const LUT: LookupTable = LookupTable::new();
+/// const LUT_CUSTOM_SEQUENCE: LutCustomSequence = LutCustomSequence::new()
+///     .sequence(Operation::EraseSector, DeviceModeSequence::new(2, 5))
+///     .sequence(Operation::PageProgram, DeviceModeSequence::new(2, 9));
+///
+/// const FLEXSPI_CONFIGURATION_BLOCK: ConfigurationBlock = ConfigurationBlock::new(LUT)
+///     .lut_custom_seq(LUT_CUSTOM_SEQUENCE);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct LutCustomSequence(pub(crate) [u8; NUMBER_OF_OPERATIONS * 4]);
+
+impl LutCustomSequence {
+    /// Create a new custom sequence table that doesn't remap any operations
+    pub const fn new() -> Self {
+        LutCustomSequence([0; NUMBER_OF_OPERATIONS * 4])
+    }
+    /// Use the LUT sequences described by `seq` for `operation`
+    ///
+    /// If `seq` doesn't describe at least one LUT sequence, or if it references a
+    /// sequence outside of the LUT, you'll observe a compile-time error.
+    pub const fn sequence(mut self, operation: Operation, seq: DeviceModeSequence) -> Self {
+        let [number_of_luts, starting_lut_index, _, _] = seq.0;
+        if number_of_luts == 0 {
+            panic!("A custom sequence must have at least one LUT sequence");
+        }
+        if starting_lut_index as usize + number_of_luts as usize > NUMBER_OF_SEQUENCES {
+            panic!("A custom sequence must be within the 16 LUT sequences");
+        }
+        let offset = operation as usize * 4;
+        self.0[offset] = number_of_luts;
+        self.0[offset + 1] = starting_lut_index;
+        self
+    }
+}
+
+impl Default for LutCustomSequence {
+    fn default() -> Self {
+        LutCustomSequence::new()
+    }
+}
+
 /// Wait time for all configuration commands
 ///
 /// From the docs...
@@ -67,7 +213,7 @@ impl Default for DeviceModeConfiguration {
 /// > status to wait until these commands complete.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
-pub struct WaitTimeConfigurationCommands(u16);
+pub struct WaitTimeConfigurationCommands(pub(crate) u16);
 impl WaitTimeConfigurationCommands {
     pub const fn disable() -> Self {
         WaitTimeConfigurationCommands(0)
@@ -80,6 +226,98 @@ impl WaitTimeConfigurationCommands {
     pub const fn new(wait_time_us: u16) -> Self {
         WaitTimeConfigurationCommands(wait_time_us / 100)
     }
+
+    /// Returns the wait time in microseconds
+    ///
+    /// A wait time of `0` indicates that the wait is disabled.
+    pub const fn wait_time_us(self) -> u16 {
+        self.0 * 100
+    }
+}
+
+/// `controllerMiscOption` bit flags
+///
+/// Combine options with [`union`](ControllerMiscOptions::union) in `const` contexts,
+/// or with `|` elsewhere. Some options are only available on certain chips.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::ControllerMiscOptions;
+///
+/// const OPTIONS: ControllerMiscOptions = ControllerMiscOptions::DDR_MODE
+///     .union(ControllerMiscOptions::SAFE_CONFIG_FREQ);
+/// assert_eq!(OPTIONS.bits(), 0x50);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ControllerMiscOptions(u32);
+
+impl ControllerMiscOptions {
+    /// Enable the differential clock
+    pub const DIFFERENTIAL_CLOCK: Self = ControllerMiscOptions(1 << 0);
+    /// Enable parallel mode
+    #[cfg(any(feature = "imxrt500", feature = "imxrt1060", feature = "imxrt1064"))]
+    pub const PARALLEL_MODE: Self = ControllerMiscOptions(1 << 2);
+    /// Enable word addressable mode
+    pub const WORD_ADDRESSABLE: Self = ControllerMiscOptions(1 << 3);
+    /// Use the safe frequency when configuring the device
+    ///
+    /// Set this for devices that support DDR read instructions.
+    pub const SAFE_CONFIG_FREQ: Self = ControllerMiscOptions(1 << 4);
+    /// Apply the pad setting overrides
+    pub const PAD_SETTING_OVERRIDE: Self = ControllerMiscOptions(1 << 5);
+    /// Enable DDR mode
+    ///
+    /// Set this for devices that support DDR read instructions.
+    pub const DDR_MODE: Self = ControllerMiscOptions(1 << 6);
+    /// Use the data valid time for all frequencies
+    #[cfg(feature = "imxrt500")]
+    pub const USE_VALID_TIME_FOR_ALL_FREQ: Self = ControllerMiscOptions(1 << 7);
+    /// Use the second pin mux group
+    #[cfg(feature = "imxrt500")]
+    pub const SECOND_PIN_MUX: Self = ControllerMiscOptions(1 << 8);
+    /// Use the second DQS pin
+    ///
+    /// If not set, the ROM uses the first DQS pin.
+    #[cfg(feature = "imxrt500")]
+    pub const SECOND_DQS_PIN_MUX: Self = ControllerMiscOptions(1 << 9);
+
+    pub(crate) const fn from_bits(bits: u32) -> Self {
+        ControllerMiscOptions(bits)
+    }
+
+    /// No options
+    pub const fn empty() -> Self {
+        ControllerMiscOptions(0)
+    }
+    /// Returns the raw `controllerMiscOption` value
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+    /// Returns `true` if all options in `other` are set in `self`
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+    /// Returns the options that are set in either `self` or `other`
+    pub const fn union(self, other: Self) -> Self {
+        ControllerMiscOptions(self.0 | other.0)
+    }
+    /// Returns the options in `self` that are not set in `other`
+    pub const fn difference(self, other: Self) -> Self {
+        ControllerMiscOptions(self.0 & !other.0)
+    }
+}
+
+impl BitOr for ControllerMiscOptions {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for ControllerMiscOptions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
 }
 
 /// `sFlashPad` field
@@ -92,6 +330,19 @@ pub enum FlashPadType {
     Octal = 8,
 }
 
+impl FlashPadType {
+    pub(crate) const fn from_raw(raw: u8) -> Option<Self> {
+        use FlashPadType::*;
+        match raw {
+            1 => Some(Single),
+            2 => Some(Dual),
+            4 => Some(Quad),
+            8 => Some(Octal),
+            _ => None,
+        }
+    }
+}
+
 /// `serialClkFreq`
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -109,6 +360,112 @@ pub enum SerialClockFrequency {
     MHz166,
 }
 
+impl SerialClockFrequency {
+    /// Returns the frequency, in MHz
+    pub const fn mhz(self) -> u16 {
+        use SerialClockFrequency::*;
+        match self {
+            MHz30 => 30,
+            MHz50 => 50,
+            MHz60 => 60,
+            #[cfg(not(feature = "imxrt500"))]
+            MHz75 => 75,
+            MHz80 => 80,
+            MHz100 => 100,
+            MHz120 => 120,
+            MHz133 => 133,
+            #[cfg(any(feature = "imxrt500", feature = "imxrt1060", feature = "imxrt1064"))]
+            MHz166 => 166,
+        }
+    }
+    pub(crate) const fn from_raw(raw: u8) -> Option<Self> {
+        use SerialClockFrequency::*;
+        match raw {
+            raw if raw == MHz30 as u8 => Some(MHz30),
+            raw if raw == MHz50 as u8 => Some(MHz50),
+            raw if raw == MHz60 as u8 => Some(MHz60),
+            #[cfg(not(feature = "imxrt500"))]
+            raw if raw == MHz75 as u8 => Some(MHz75),
+            raw if raw == MHz80 as u8 => Some(MHz80),
+            raw if raw == MHz100 as u8 => Some(MHz100),
+            raw if raw == MHz120 as u8 => Some(MHz120),
+            raw if raw == MHz133 as u8 => Some(MHz133),
+            #[cfg(any(feature = "imxrt500", feature = "imxrt1060", feature = "imxrt1064"))]
+            raw if raw == MHz166 as u8 => Some(MHz166),
+            _ => None,
+        }
+    }
+}
+
+/// `busyBitPolarity`, the meaning of the busy bit in the status register
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum BusyBitPolarity {
+    /// The busy bit is 1 when the device is busy
+    BusyWhenSet = 0,
+    /// The busy bit is 0 when the device is busy
+    BusyWhenClear = 1,
+}
+
+impl BusyBitPolarity {
+    /// Any non-zero value means "busy when clear"
+    pub(crate) const fn from_raw(raw: u16) -> Self {
+        match raw {
+            0 => BusyBitPolarity::BusyWhenSet,
+            _ => BusyBitPolarity::BusyWhenClear,
+        }
+    }
+}
+
+/// The largest `busyOffset`
+///
+/// The ROM reads the busy bit from a 32 bit status word, whatever the width of
+/// the device's status register.
+pub(crate) const MAX_BUSY_OFFSET: u16 = 31;
+
+/// `dataValidTime`, the time from a clock edge to valid data for ports A and B
+///
+/// The ROM uses the data valid time to configure the FlexSPI DLL. The value is
+/// kept in units of 0.1ns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DataValidTime {
+    port_a: u16,
+    port_b: u16,
+}
+
+impl DataValidTime {
+    /// Create a data valid time from whole nanoseconds
+    ///
+    /// If a time can't be represented, you'll observe a compile-time error.
+    pub const fn new(port_a_ns: u16, port_b_ns: u16) -> Self {
+        if port_a_ns > u16::MAX / 10 || port_b_ns > u16::MAX / 10 {
+            panic!("Data valid time is too large");
+        }
+        DataValidTime::from_tenths_ns(port_a_ns * 10, port_b_ns * 10)
+    }
+    /// Create a data valid time from tenths of a nanosecond
+    ///
+    /// Use this for sub-nanosecond precision. For example, `from_tenths_ns(16, 16)`
+    /// represents 1.6ns for both ports.
+    pub const fn from_tenths_ns(port_a: u16, port_b: u16) -> Self {
+        DataValidTime { port_a, port_b }
+    }
+    /// Returns the port A data valid time, in tenths of a nanosecond
+    pub const fn port_a(self) -> u16 {
+        self.port_a
+    }
+    /// Returns the port B data valid time, in tenths of a nanosecond
+    pub const fn port_b(self) -> u16 {
+        self.port_b
+    }
+    pub(crate) const fn raw(self) -> u32 {
+        ((self.port_b as u32) << 16) | self.port_a as u32
+    }
+    pub(crate) const fn from_raw(raw: u32) -> Self {
+        DataValidTime::from_tenths_ns(raw as u16, (raw >> 16) as u16)
+    }
+}
+
 /// A FlexSPI serial flash region
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(usize)]
@@ -121,8 +478,26 @@ pub enum SerialFlashRegion {
 
 #[cfg(test)]
 mod test {
+    use super::ControllerMiscOptions;
+    #[cfg(any(feature = "imxrt1060", feature = "imxrt500"))]
     use super::SerialClockFrequency;
 
+    #[test]
+    fn controller_misc_options() {
+        let options = ControllerMiscOptions::DIFFERENTIAL_CLOCK | ControllerMiscOptions::DDR_MODE;
+        assert_eq!(options.bits(), 0x41);
+        assert!(options.contains(ControllerMiscOptions::DDR_MODE));
+        assert!(!options.contains(ControllerMiscOptions::WORD_ADDRESSABLE));
+        assert_eq!(
+            options.difference(ControllerMiscOptions::DDR_MODE),
+            ControllerMiscOptions::DIFFERENTIAL_CLOCK
+        );
+        assert_eq!(
+            ControllerMiscOptions::default(),
+            ControllerMiscOptions::empty()
+        );
+    }
+
     #[test]
     #[cfg(feature = "imxrt1060")]
     fn serial_clk_freq() {
@@ -1,27 +1,334 @@
 //! FlexSPI Lookup table
 
-use super::sequence::{Sequence, SEQUENCE_SIZE};
+use core::fmt;
+
+use super::opcodes::{ddr, sdr};
+use super::sequence::{MultiSequence, Opcode, Sequence, INSTRUCTIONS_PER_SEQUENCE, SEQUENCE_SIZE};
+use super::{ContinuousRead, JUMP_ON_CS, STOP};
 
 /// The default sequence definition lookup indices
 ///
 /// `Command`s are looked up by the processor when it needs to
 /// interact with the flash chip. The enumeration lets us index back into
 /// the `Lookup` struct, and associate a sequence command for that action.
+///
+/// The named variants are the serial NOR indices. Use [`Command::Nand`] for
+/// the serial NAND indices, and [`Command::Custom`] for any other index, like
+/// the index of a device mode or configuration command sequence.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(usize)]
 pub enum Command {
-    Read = 0,
+    /// Index 0
+    Read,
+    /// Index 1
+    ReadStatus,
+    /// Index 2
+    ReadStatusXpi,
+    /// Index 3
+    WriteEnable,
+    /// Index 4
+    WriteEnableXpi,
+    /// Index 5
+    EraseSector,
+    /// Index 8
+    EraseBlock,
+    /// Index 9
+    PageProgram,
+    /// Index 11
+    ChipErase,
+    /// Index 13
+    ReadSfdp,
+    /// Index 14
+    RestoreNoCmd,
+    /// Index 15
+    ExitNoCmd,
+    /// Index 15, the same index as [`Command::ExitNoCmd`]
+    Dummy,
+    /// A serial NAND command
+    Nand(NandCommand),
+    /// Any LUT index
+    ///
+    /// If the index is 16 or more, you'll observe a compile-time error.
+    Custom(u8),
+}
+
+impl Command {
+    /// Returns the command for a LUT `index`
+    ///
+    /// Serial NOR commands are preferred. Index 15 is [`Command::ExitNoCmd`], and
+    /// unnamed indices are [`Command::Custom`]. If `index` is 16 or more, you'll
+    /// observe a compile-time error.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::Command;
+    ///
+    /// assert_eq!(Command::from_index(9), Command::PageProgram);
+    /// assert_eq!(Command::from_index(6), Command::Custom(6));
+    /// ```
+    pub const fn from_index(index: usize) -> Self {
+        match index {
+            0 => Command::Read,
+            1 => Command::ReadStatus,
+            2 => Command::ReadStatusXpi,
+            3 => Command::WriteEnable,
+            4 => Command::WriteEnableXpi,
+            5 => Command::EraseSector,
+            8 => Command::EraseBlock,
+            9 => Command::PageProgram,
+            11 => Command::ChipErase,
+            13 => Command::ReadSfdp,
+            14 => Command::RestoreNoCmd,
+            15 => Command::ExitNoCmd,
+            index if index < NUMBER_OF_SEQUENCES => Command::Custom(index as u8),
+            _ => panic!("A LUT index must be less than 16"),
+        }
+    }
+    /// Returns the LUT index of the command
+    ///
+    /// If the command is a [`Command::Custom`] index that's outside of the LUT,
+    /// you'll observe a compile-time error.
+    pub const fn index(self) -> usize {
+        match self {
+            Command::Read => 0,
+            Command::ReadStatus => 1,
+            Command::ReadStatusXpi => 2,
+            Command::WriteEnable => 3,
+            Command::WriteEnableXpi => 4,
+            Command::EraseSector => 5,
+            Command::EraseBlock => 8,
+            Command::PageProgram => 9,
+            Command::ChipErase => 11,
+            Command::ReadSfdp => 13,
+            Command::RestoreNoCmd => 14,
+            Command::ExitNoCmd | Command::Dummy => 15,
+            Command::Nand(cmd) => cmd as usize,
+            Command::Custom(idx) => {
+                if idx as usize >= NUMBER_OF_SEQUENCES {
+                    panic!("A custom command index must be less than 16");
+                }
+                idx as usize
+            }
+        }
+    }
+}
+
+/// A disagreement between a [`Command`] and its [`Sequence`]
+///
+/// See [`Command::check_sequence`] for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SequenceError {
+    /// The sequence has no `READ` instruction
+    MissingRead,
+    /// The sequence has no `WRITE` instruction
+    MissingWrite,
+    /// The sequence has no `RADDR` instruction
+    MissingRowAddress,
+    /// The sequence has a `RADDR` or `CADDR` instruction
+    UnexpectedAddress,
+    /// The sequence has a `READ` instruction
+    UnexpectedRead,
+    /// The sequence has a `WRITE` instruction
+    UnexpectedWrite,
+}
+
+/// Every sequence error, in declaration order
+const SEQUENCE_ERRORS: [SequenceError; 6] = [
+    SequenceError::MissingRead,
+    SequenceError::MissingWrite,
+    SequenceError::MissingRowAddress,
+    SequenceError::UnexpectedAddress,
+    SequenceError::UnexpectedRead,
+    SequenceError::UnexpectedWrite,
+];
+
+impl SequenceError {
+    /// Returns a message that describes the error
+    pub const fn message(self) -> &'static str {
+        match self {
+            SequenceError::MissingRead => "The command's sequence needs a READ instruction",
+            SequenceError::MissingWrite => "The command's sequence needs a WRITE instruction",
+            SequenceError::MissingRowAddress => "The command's sequence needs a RADDR instruction",
+            SequenceError::UnexpectedAddress => {
+                "The command's sequence must not have a RADDR or CADDR instruction"
+            }
+            SequenceError::UnexpectedRead => {
+                "The command's sequence must not have a READ instruction"
+            }
+            SequenceError::UnexpectedWrite => {
+                "The command's sequence must not have a WRITE instruction"
+            }
+        }
+    }
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+/// All of the [`SequenceError`]s of a sequence
+///
+/// Use [`Command::sequence_errors`] to find the errors. The iterator yields the errors
+/// in the order that `SequenceError` declares them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceErrors(u8);
+
+impl SequenceErrors {
+    const fn insert(self, err: SequenceError) -> Self {
+        SequenceErrors(self.0 | 1 << err as u8)
+    }
+    /// Returns `true` if there are no errors
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+    /// Returns `true` if the set has the error
+    pub const fn contains(self, err: SequenceError) -> bool {
+        self.0 & (1 << err as u8) != 0
+    }
+    /// Returns the first error, or `None` if there are no errors
+    pub const fn first(self) -> Option<SequenceError> {
+        let mut idx = 0;
+        while idx < SEQUENCE_ERRORS.len() {
+            if self.contains(SEQUENCE_ERRORS[idx]) {
+                return Some(SEQUENCE_ERRORS[idx]);
+            }
+            idx += 1;
+        }
+        None
+    }
+}
+
+impl Iterator for SequenceErrors {
+    type Item = SequenceError;
+    fn next(&mut self) -> Option<SequenceError> {
+        let err = self.first()?;
+        self.0 &= !(1 << err as u8);
+        Some(err)
+    }
+}
+
+/// Returns `true` if the sequence executes the SDR or DDR `opcode`
+const fn executes(sequence: &Sequence, sdr: Opcode, ddr: Opcode) -> bool {
+    let mut idx = 0;
+    while idx < INSTRUCTIONS_PER_SEQUENCE {
+        let opcode = sequence.0[idx].opcode();
+        if opcode.is(STOP.opcode()) || opcode.is(JUMP_ON_CS.opcode()) {
+            return false;
+        }
+        if opcode.is(sdr) || opcode.is(ddr) {
+            return true;
+        }
+        idx += 1;
+    }
+    false
+}
+
+impl Command {
+    /// Check that `sequence` has the shape that the ROM expects for this command
+    ///
+    /// | Command                               | Rule                                       |
+    /// | ------------------------------------- | ------------------------------------------ |
+    /// | `Read`, `ReadStatus`, `ReadStatusXpi` | needs `READ`, no `WRITE`                   |
+    /// | `WriteEnable`, `WriteEnableXpi`       | no `RADDR`, `CADDR`, `READ` or `WRITE`     |
+    /// | `EraseSector`, `EraseBlock`           | needs `RADDR`, no `READ` or `WRITE`        |
+    /// | `PageProgram`                         | needs `RADDR` and `WRITE`, no `READ`       |
+    /// | `ChipErase`                           | no `RADDR`, `CADDR`, `READ` or `WRITE`     |
+    ///
+    /// Both SDR and DDR opcodes satisfy the rules. Instructions after a `STOP` or
+    /// `JUMP_ON_CS` are ignored. There are no rules for any other command.
+    ///
+    /// If the sequence breaks more than one rule, this returns the first error, in
+    /// the order that `SequenceError` declares them. Use
+    /// [`sequence_errors`](Command::sequence_errors) to find all of the errors.
+    pub const fn check_sequence(self, sequence: &Sequence) -> Result<(), SequenceError> {
+        match self.sequence_errors(sequence).first() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+    /// Returns all of the ways that `sequence` breaks the command's rules
+    ///
+    /// See [`check_sequence`](Command::check_sequence) for the rules.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{Command, Instr, Pads, SequenceBuilder, SequenceError};
+    /// use imxrt_boot_gen::flexspi::AddressBits;
+    ///
+    /// const PAGE_PROGRAM: imxrt_boot_gen::flexspi::Sequence = SequenceBuilder::new()
+    ///     .instr(Instr::cmd(Pads::One, 0x02))
+    ///     .instr(Instr::raddr(Pads::One, AddressBits::B24))
+    ///     .instr(Instr::write(Pads::One))
+    ///     .build();
+    ///
+    /// let errors: Vec<SequenceError> = Command::ChipErase.sequence_errors(&PAGE_PROGRAM).collect();
+    /// assert_eq!(errors, [SequenceError::UnexpectedAddress, SequenceError::UnexpectedWrite]);
+    /// ```
+    pub const fn sequence_errors(self, sequence: &Sequence) -> SequenceErrors {
+        let read = executes(sequence, sdr::READ, ddr::READ);
+        let write = executes(sequence, sdr::WRITE, ddr::WRITE);
+        let row_address = executes(sequence, sdr::RADDR, ddr::RADDR);
+        let address = row_address || executes(sequence, sdr::CADDR, ddr::CADDR);
+
+        let (needs_read, needs_write, needs_row_address, allows_address, allows_read, allows_write) =
+            match self {
+                Command::Read | Command::ReadStatus | Command::ReadStatusXpi => {
+                    (true, false, false, true, true, false)
+                }
+                Command::WriteEnable | Command::WriteEnableXpi | Command::ChipErase => {
+                    (false, false, false, false, false, false)
+                }
+                Command::EraseSector | Command::EraseBlock => {
+                    (false, false, true, true, false, false)
+                }
+                Command::PageProgram => (false, true, true, true, false, true),
+                _ => (false, false, false, true, true, true),
+            };
+
+        let mut errors = SequenceErrors(0);
+        if needs_read && !read {
+            errors = errors.insert(SequenceError::MissingRead);
+        }
+        if needs_write && !write {
+            errors = errors.insert(SequenceError::MissingWrite);
+        }
+        if needs_row_address && !row_address {
+            errors = errors.insert(SequenceError::MissingRowAddress);
+        }
+        if !allows_address && address {
+            errors = errors.insert(SequenceError::UnexpectedAddress);
+        }
+        if !allows_read && read {
+            errors = errors.insert(SequenceError::UnexpectedRead);
+        }
+        if !allows_write && write {
+            errors = errors.insert(SequenceError::UnexpectedWrite);
+        }
+        errors
+    }
+}
+
+/// The serial NAND sequence definition lookup indices
+///
+/// Use a `NandCommand` with [`Command::Nand`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(usize)]
+pub enum NandCommand {
+    ReadFromCache = 0,
     ReadStatus = 1,
+    ReadEccStatus = 2,
     WriteEnable = 3,
-    EraseSector = 5,
-    PageProgram = 9,
-    ChipErase = 11,
-    Dummy = 15,
+    ReadFromCacheOdd = 4,
+    EraseBlock = 5,
+    ProgramLoad = 6,
+    ProgramLoadOdd = 7,
+    ReadPage = 8,
+    ProgramExecute = 9,
 }
 
 /// Size of the lookup table in bytes
-const LOOKUP_TABLE_SIZE_BYTES: usize = 256;
-const NUMBER_OF_SEQUENCES: usize = LOOKUP_TABLE_SIZE_BYTES / SEQUENCE_SIZE;
+pub(crate) const LOOKUP_TABLE_SIZE_BYTES: usize = 256;
+pub(crate) const NUMBER_OF_SEQUENCES: usize = LOOKUP_TABLE_SIZE_BYTES / SEQUENCE_SIZE;
 
 /// A sequence lookup table, part of the general FlexSPI configuration block
 ///
@@ -47,25 +354,240 @@ const NUMBER_OF_SEQUENCES: usize = LOOKUP_TABLE_SIZE_BYTES / SEQUENCE_SIZE;
 ///         .build());
 /// ```
 #[derive(Debug, Clone, Copy)]
-#[repr(transparent)]
-pub struct LookupTable([Sequence; NUMBER_OF_SEQUENCES]);
+pub struct LookupTable {
+    pub(crate) sequences: [Sequence; NUMBER_OF_SEQUENCES],
+    /// A bit for each index that a command or multi-sequence assigned
+    assigned: u16,
+    /// A bit for each index that a multi-sequence assigned
+    multi_sequences: u16,
+}
 
 impl LookupTable {
     /// Create a new lookup table. All memory is set to zero.
+    #[allow(clippy::new_without_default)]
     pub const fn new() -> Self {
-        LookupTable([Sequence::stopped(); NUMBER_OF_SEQUENCES])
+        LookupTable {
+            sequences: [Sequence::stopped(); NUMBER_OF_SEQUENCES],
+            assigned: 0,
+            multi_sequences: 0,
+        }
+    }
+    /// Create a lookup table from `sequences`
+    ///
+    /// Every populated sequence counts as assigned.
+    pub(crate) const fn from_sequences(sequences: [Sequence; NUMBER_OF_SEQUENCES]) -> Self {
+        let mut assigned = 0;
+        let mut idx = 0;
+        while idx < NUMBER_OF_SEQUENCES {
+            if !sequences[idx].is_stopped() {
+                assigned |= 1 << idx;
+            }
+            idx += 1;
+        }
+        LookupTable {
+            sequences,
+            assigned,
+            multi_sequences: 0,
+        }
     }
     /// Assign the `sequence` to the command that is found at the `Command` index
+    ///
+    /// Assigning a command again replaces its sequence. If a multi-sequence already
+    /// uses the command's index, you'll observe a compile-time error.
     pub const fn command(mut self, cmd: Command, sequence: Sequence) -> Self {
-        self.0[cmd as usize] = sequence;
+        let idx = cmd.index();
+        if self.multi_sequences & (1 << idx) != 0 {
+            panic!("The command overlaps a multi-sequence");
+        }
+        self.sequences[idx] = sequence;
+        self.assigned |= 1 << idx;
+        self
+    }
+    /// Assign the `sequence` to the command, after checking the sequence
+    ///
+    /// If the sequence doesn't have the shape that the command expects, you'll observe
+    /// a compile-time error. See [`Command::check_sequence`] for the rules.
+    pub const fn checked_command(self, cmd: Command, sequence: Sequence) -> Self {
+        match cmd.check_sequence(&sequence) {
+            Ok(()) => self.command(cmd, sequence),
+            Err(err) => panic!("{}", err.message()),
+        }
+    }
+    /// Assign the sequences of the `multi_sequence` to consecutive LUT indices
+    ///
+    /// If a command or another multi-sequence already uses any of the multi-sequence's
+    /// indices, you'll observe a compile-time error. Assigning a command to one of the
+    /// indices afterwards is also a compile-time error. A command counts as assigned
+    /// even if its sequence is empty.
+    pub const fn multi_sequence(mut self, multi_sequence: MultiSequence) -> Self {
+        let start = multi_sequence.starting_lut_index as usize;
+        let mut idx = 0;
+        while idx < multi_sequence.number_of_luts as usize {
+            let bit = 1 << (start + idx);
+            if self.assigned & bit != 0 {
+                panic!("The multi-sequence overlaps an assigned LUT sequence");
+            }
+            self.sequences[start + idx] = multi_sequence.sequences[idx];
+            self.assigned |= bit;
+            self.multi_sequences |= bit;
+            idx += 1;
+        }
         self
     }
+    /// Assign the `Read`, `RestoreNoCmd` and `ExitNoCmd` sequences for XIP continuous
+    /// read mode
+    ///
+    /// Any sequences already assigned to those commands are replaced.
+    pub const fn continuous_read(self, read: ContinuousRead) -> Self {
+        self.command(Command::Read, read.read_sequence())
+            .command(Command::RestoreNoCmd, read.restore_sequence())
+            .command(Command::ExitNoCmd, read.exit_sequence())
+    }
+    /// Returns the sequence at the `Command` index
+    pub const fn get(&self, cmd: Command) -> &Sequence {
+        &self.sequences[cmd.index()]
+    }
+    /// Returns an iterator over the populated sequences
+    ///
+    /// A sequence is populated if any of its instructions is not a `STOP`. Each
+    /// sequence is paired with the command from [`Command::from_index`].
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{Command, Instr, LookupTable, Pads, SequenceBuilder, opcodes::sdr::*};
+    ///
+    /// const LUT: LookupTable = LookupTable::new()
+    ///     .command(Command::WriteEnable, SequenceBuilder::new()
+    ///         .instr(Instr::new(CMD, Pads::One, 0x06))
+    ///         .build());
+    ///
+    /// let commands: Vec<Command> = LUT.iter().map(|(cmd, _)| cmd).collect();
+    /// assert_eq!(commands, [Command::WriteEnable]);
+    /// ```
+    pub const fn iter(&self) -> Sequences<'_> {
+        Sequences {
+            lookup_table: self,
+            idx: 0,
+        }
+    }
+    /// Returns `true` if the `Command` index holds a sequence
+    ///
+    /// An index holds a sequence if any of its instructions is not a STOP.
+    pub(crate) const fn is_populated(&self, cmd: Command) -> bool {
+        !self.sequences[cmd.index()].is_stopped()
+    }
+    /// Returns the little-endian memory representation of the lookup table
+    pub const fn to_bytes(&self) -> [u8; LOOKUP_TABLE_SIZE_BYTES] {
+        self.encode([0; LOOKUP_TABLE_SIZE_BYTES], 0)
+    }
+    /// Encode the lookup table into `bytes`, starting at `offset`
+    pub(crate) const fn encode<const N: usize>(
+        &self,
+        mut bytes: [u8; N],
+        offset: usize,
+    ) -> [u8; N] {
+        let mut idx = 0;
+        while idx < NUMBER_OF_SEQUENCES {
+            bytes = self.sequences[idx].encode(bytes, offset + idx * SEQUENCE_SIZE);
+            idx += 1;
+        }
+        bytes
+    }
+    /// Decode a lookup table from the `LOOKUP_TABLE_SIZE_BYTES` bytes found at `offset`
+    pub(crate) const fn decode(bytes: &[u8], offset: usize) -> Self {
+        let mut sequences = [Sequence::stopped(); NUMBER_OF_SEQUENCES];
+        let mut idx = 0;
+        while idx < NUMBER_OF_SEQUENCES {
+            sequences[idx] = Sequence::decode(bytes, offset + idx * SEQUENCE_SIZE);
+            idx += 1;
+        }
+        LookupTable::from_sequences(sequences)
+    }
+}
+
+/// Displays every populated sequence, with its [`Command`], one instruction per line
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{Command, Instr, LookupTable, Pads, SequenceBuilder, opcodes::sdr::*};
+///
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::ReadStatus, SequenceBuilder::new()
+///         .instr(Instr::new(CMD, Pads::One, 0x05))
+///         .instr(Instr::new(READ, Pads::One, 0x04))
+///         .build())
+///     .command(Command::WriteEnable, SequenceBuilder::new()
+///         .instr(Instr::new(CMD, Pads::One, 0x06))
+///         .build());
+///
+/// assert_eq!(
+///     LUT.to_string(),
+///     "[1] ReadStatus\n\
+///      \x20   CMD_SDR SINGLE 0x05\n\
+///      \x20   READ_SDR SINGLE 0x04\n\
+///      \x20   STOP (x6)\n\
+///      [3] WriteEnable\n\
+///      \x20   CMD_SDR SINGLE 0x06\n\
+///      \x20   STOP (x7)"
+/// );
+/// ```
+impl fmt::Display for LookupTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, (cmd, sequence)) in self.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[{}] {:?}\n    ", cmd.index(), cmd)?;
+            sequence.fmt_lines(f, "\n    ")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a LookupTable {
+    type Item = (Command, &'a Sequence);
+    type IntoIter = Sequences<'a>;
+    fn into_iter(self) -> Sequences<'a> {
+        self.iter()
+    }
 }
 
+/// An iterator over the populated sequences of a [`LookupTable`]
+///
+/// Use [`LookupTable::iter`] to create the iterator.
+#[derive(Debug, Clone)]
+pub struct Sequences<'a> {
+    lookup_table: &'a LookupTable,
+    idx: usize,
+}
+
+impl<'a> Iterator for Sequences<'a> {
+    type Item = (Command, &'a Sequence);
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx < NUMBER_OF_SEQUENCES {
+            let idx = self.idx;
+            self.idx += 1;
+            let sequence = &self.lookup_table.sequences[idx];
+            if !sequence.is_stopped() {
+                return Some((Command::from_index(idx), sequence));
+            }
+        }
+        None
+    }
+}
+
+/// Lookup tables are equal if their sequences are equal
+impl PartialEq for LookupTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.sequences == other.sequences
+    }
+}
+
+impl Eq for LookupTable {}
+
 #[cfg(test)]
 mod test {
-    use super::{Command, LookupTable};
-    use crate::flexspi::sequence::SequenceBuilder;
+    use super::{Command, LookupTable, NandCommand, SequenceError};
+    use crate::flexspi::sequence::{MultiSequenceBuilder, SequenceBuilder};
+    use crate::flexspi::{opcodes::sdr::*, Instr, Pads, Sequence, STOP};
 
     #[test]
     fn smoke() {
@@ -78,4 +600,292 @@ mod test {
             .command(Command::ChipErase, SequenceBuilder::new().build())
             .command(Command::Dummy, SequenceBuilder::new().build());
     }
+
+    #[test]
+    fn indices() {
+        const SEQ: crate::flexspi::Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x5A))
+            .build();
+        const LUT: LookupTable = LookupTable::new()
+            .command(Command::ReadSfdp, SEQ)
+            .command(Command::Nand(NandCommand::ProgramExecute), SEQ)
+            .command(Command::Custom(7), SEQ);
+        for (idx, seq) in LUT.sequences.iter().enumerate() {
+            if [7, 9, 13].contains(&idx) {
+                assert_eq!(seq, &SEQ);
+            } else {
+                assert_eq!(seq, &LookupTable::new().sequences[0]);
+            }
+        }
+        assert_eq!(Command::Dummy.index(), Command::ExitNoCmd.index());
+    }
+
+    #[test]
+    fn check_sequence() {
+        const ERASE: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x20))
+            .instr(Instr::new(RADDR, Pads::One, 0x18))
+            .build();
+        const PROGRAM: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x02))
+            .instr(Instr::new(RADDR, Pads::One, 0x18))
+            .instr(Instr::new(WRITE, Pads::One, 0x04))
+            .build();
+        const WRITE_ENABLE: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x06))
+            .build();
+        const _LUT: LookupTable = LookupTable::new()
+            .checked_command(Command::EraseSector, ERASE)
+            .checked_command(Command::PageProgram, PROGRAM)
+            .checked_command(Command::WriteEnable, WRITE_ENABLE);
+
+        assert_eq!(Command::PageProgram.check_sequence(&PROGRAM), Ok(()));
+        assert_eq!(
+            Command::PageProgram.check_sequence(&ERASE),
+            Err(SequenceError::MissingWrite)
+        );
+        assert_eq!(
+            Command::EraseSector.check_sequence(&WRITE_ENABLE),
+            Err(SequenceError::MissingRowAddress)
+        );
+        assert_eq!(
+            Command::WriteEnable.check_sequence(&ERASE),
+            Err(SequenceError::UnexpectedAddress)
+        );
+        assert_eq!(
+            Command::ReadStatus.check_sequence(&WRITE_ENABLE),
+            Err(SequenceError::MissingRead)
+        );
+        assert_eq!(Command::Custom(7).check_sequence(&ERASE), Ok(()));
+
+        // A page program sequence in the erase slot, and the other way around
+        assert_eq!(
+            Command::EraseSector.check_sequence(&PROGRAM),
+            Err(SequenceError::UnexpectedWrite)
+        );
+        assert_eq!(
+            Command::EraseBlock.check_sequence(&PROGRAM),
+            Err(SequenceError::UnexpectedWrite)
+        );
+        const READ_STATUS: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x05))
+            .instr(Instr::new(READ, Pads::One, 0x04))
+            .build();
+        assert_eq!(
+            Command::WriteEnable.check_sequence(&READ_STATUS),
+            Err(SequenceError::UnexpectedRead)
+        );
+        assert_eq!(
+            Command::PageProgram.check_sequence(&READ_STATUS),
+            Err(SequenceError::MissingWrite)
+        );
+        assert_eq!(
+            Command::Read.check_sequence(&PROGRAM),
+            Err(SequenceError::MissingRead)
+        );
+
+        // All errors
+        let errors: Vec<_> = Command::PageProgram.sequence_errors(&READ_STATUS).collect();
+        assert_eq!(
+            errors,
+            [
+                SequenceError::MissingWrite,
+                SequenceError::MissingRowAddress,
+                SequenceError::UnexpectedRead
+            ]
+        );
+        let errors = Command::ChipErase.sequence_errors(&PROGRAM);
+        assert!(errors.contains(SequenceError::UnexpectedAddress));
+        assert!(errors.contains(SequenceError::UnexpectedWrite));
+        assert_eq!(errors.count(), 2);
+        assert!(Command::EraseSector.sequence_errors(&ERASE).is_empty());
+
+        // Instructions after a STOP never execute
+        const STOPPED: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x05))
+            .instr(STOP)
+            .instr(Instr::new(READ, Pads::One, 0x04))
+            .build();
+        assert_eq!(
+            Command::ReadStatus.check_sequence(&STOPPED),
+            Err(SequenceError::MissingRead)
+        );
+    }
+
+    #[test]
+    fn get_and_iter() {
+        const SEQ: crate::flexspi::Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x5A))
+            .build();
+        const LUT: LookupTable = LookupTable::new()
+            .command(Command::ReadSfdp, SEQ)
+            .command(Command::Custom(6), SEQ)
+            .command(Command::Dummy, SEQ);
+        assert_eq!(LUT.get(Command::ReadSfdp), &SEQ);
+        assert!(LUT.get(Command::Read).is_stopped());
+        let commands: Vec<_> = LUT.iter().map(|(cmd, _)| cmd).collect();
+        assert_eq!(
+            commands,
+            [Command::Custom(6), Command::ReadSfdp, Command::ExitNoCmd]
+        );
+        for idx in 0..16 {
+            assert_eq!(Command::from_index(idx).index(), idx);
+        }
+    }
+
+    #[test]
+    fn multi_sequence() {
+        const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+        const LUT: LookupTable = LookupTable::new().multi_sequence(
+            MultiSequenceBuilder::new(14)
+                .instr(INSTR)
+                .instr(INSTR)
+                .instr(INSTR)
+                .instr(INSTR)
+                .instr(INSTR)
+                .instr(INSTR)
+                .instr(INSTR)
+                .instr(INSTR)
+                .instr(INSTR)
+                .build(),
+        );
+        assert_eq!(LUT.sequences[14].0, [INSTR; 8]);
+        assert_eq!(LUT.sequences[15].0[0], INSTR);
+        assert!(LUT.sequences[13].is_stopped());
+    }
 }
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::*;
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::Custom(15), SequenceBuilder::new().build());
+/// ```
+#[cfg(doctest)]
+struct CustomCommandLimit;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::*;
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::Custom(16), SequenceBuilder::new().build()); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct CustomCommandOutOfBounds;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::WriteEnable, SequenceBuilder::new().instr(INSTR).build())
+///     .multi_sequence(MultiSequenceBuilder::new(4).instr(INSTR).build());
+/// ```
+#[cfg(doctest)]
+struct MultiSequenceAdjacent;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::WriteEnable, SequenceBuilder::new().instr(INSTR).build())
+///     .multi_sequence(MultiSequenceBuilder::new(3).instr(INSTR).build()); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct MultiSequenceOverlap;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+/// const LUT: LookupTable = LookupTable::new()
+///     .multi_sequence(MultiSequenceBuilder::new(4).instr(INSTR).build())
+///     .command(Command::WriteEnable, SequenceBuilder::new().instr(INSTR).build());
+/// ```
+#[cfg(doctest)]
+struct CommandAfterMultiSequenceAdjacent;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+/// const LUT: LookupTable = LookupTable::new()
+///     .multi_sequence(MultiSequenceBuilder::new(3).instr(INSTR).build())
+///     .command(Command::WriteEnable, SequenceBuilder::new().instr(INSTR).build()); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct CommandAfterMultiSequenceOverlap;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::WriteEnable, SequenceBuilder::new().build())
+///     .multi_sequence(MultiSequenceBuilder::new(4).instr(INSTR).build());
+/// ```
+#[cfg(doctest)]
+struct MultiSequenceAfterEmptyCommandAdjacent;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::WriteEnable, SequenceBuilder::new().build())
+///     .multi_sequence(MultiSequenceBuilder::new(3).instr(INSTR).build()); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct MultiSequenceAfterEmptyCommandOverlap;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const ERASE: Sequence = SequenceBuilder::new()
+///     .instr(Instr::new(CMD, Pads::One, 0x20))
+///     .instr(Instr::new(RADDR, Pads::One, 0x18))
+///     .build();
+/// const LUT: LookupTable = LookupTable::new()
+///     .checked_command(Command::EraseSector, ERASE);
+/// ```
+#[cfg(doctest)]
+struct CheckedCommand;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const ERASE: Sequence = SequenceBuilder::new()
+///     .instr(Instr::new(CMD, Pads::One, 0x20))
+///     .instr(Instr::new(RADDR, Pads::One, 0x18))
+///     .build();
+/// const LUT: LookupTable = LookupTable::new()
+///     .checked_command(Command::PageProgram, ERASE); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct CheckedCommandMismatch;
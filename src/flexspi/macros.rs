@@ -0,0 +1,226 @@
+//! Support for the [`sequence!`](crate::sequence) and [`lut!`](crate::lut) macros
+//!
+//! Nothing in this module is part of the public API.
+
+use super::{lookup::NUMBER_OF_SEQUENCES, Command};
+
+pub use super::opcodes::ddr::{
+    CADDR as CADDR_DDR, CMD as CMD_DDR, DATASZ as DATASZ_DDR, DUMMY as DUMMY_DDR,
+    DUMMY_RWDS as DUMMY_RWDS_DDR, LEARN as LEARN_DDR, MODE1 as MODE1_DDR, MODE2 as MODE2_DDR,
+    MODE4 as MODE4_DDR, MODE8 as MODE8_DDR, RADDR as RADDR_DDR, READ as READ_DDR,
+    WRITE as WRITE_DDR,
+};
+pub use super::opcodes::sdr::{
+    CADDR as CADDR_SDR, CMD as CMD_SDR, DATASZ as DATASZ_SDR, DUMMY as DUMMY_SDR,
+    DUMMY_RWDS as DUMMY_RWDS_SDR, LEARN as LEARN_SDR, MODE1 as MODE1_SDR, MODE2 as MODE2_SDR,
+    MODE4 as MODE4_SDR, MODE8 as MODE8_SDR, RADDR as RADDR_SDR, READ as READ_SDR,
+    WRITE as WRITE_SDR,
+};
+
+/// Panics if two commands share a LUT index
+pub const fn assert_unique_commands(commands: &[Command]) {
+    let mut assigned = [false; NUMBER_OF_SEQUENCES];
+    let mut idx = 0;
+    while idx < commands.len() {
+        let index = commands[idx].index();
+        if assigned[index] {
+            panic!("Two commands in lut! share the same LUT index");
+        }
+        assigned[index] = true;
+        idx += 1;
+    }
+}
+
+/// Create a [`Sequence`](crate::flexspi::Sequence) from a list of instructions
+///
+/// Each instruction is an opcode, the pads, and an operand. Opcodes use the names
+/// from the [`Opcode`](crate::flexspi::Opcode) `Display` implementation, like
+/// `CMD_SDR` and `READ_DDR`. Pads are `x1`, `x2`, `x4` or `x8`. The operand is any
+/// `const` `u8` expression.
+///
+/// The sequence is always evaluated at compile time. If you provide more than eight
+/// instructions, you'll observe a compile-time error.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{Instr, Pads, Sequence, SequenceBuilder, opcodes::sdr::*};
+/// use imxrt_boot_gen::sequence;
+///
+/// const SEQ_READ: Sequence = sequence![CMD_SDR x1 0xEB, RADDR_SDR x4 24, DUMMY_SDR x4 6, READ_SDR x4 4];
+///
+/// assert_eq!(
+///     SEQ_READ,
+///     SequenceBuilder::new()
+///         .instr(Instr::new(CMD, Pads::One, 0xEB))
+///         .instr(Instr::new(RADDR, Pads::Four, 24))
+///         .instr(Instr::new(DUMMY, Pads::Four, 6))
+///         .instr(Instr::new(READ, Pads::Four, 4))
+///         .build()
+/// );
+/// ```
+#[macro_export]
+macro_rules! sequence {
+    ($($opcode:ident $pads:ident $operand:expr),* $(,)?) => {{
+        const SEQUENCE: $crate::flexspi::Sequence = $crate::flexspi::SequenceBuilder::new()
+            $(.instr($crate::flexspi::Instr::new(
+                $crate::flexspi::macros::$opcode,
+                $crate::__flexspi_pads!($pads),
+                $operand,
+            )))*
+            .build();
+        SEQUENCE
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __flexspi_pads {
+    (x1) => {
+        $crate::flexspi::Pads::One
+    };
+    (x2) => {
+        $crate::flexspi::Pads::Two
+    };
+    (x4) => {
+        $crate::flexspi::Pads::Four
+    };
+    (x8) => {
+        $crate::flexspi::Pads::Eight
+    };
+    ($pads:ident) => {
+        compile_error!(concat!(
+            "Unknown pads '",
+            stringify!($pads),
+            "'; expected x1, x2, x4 or x8"
+        ))
+    };
+}
+
+/// Create a [`LookupTable`](crate::flexspi::LookupTable) from commands and their
+/// instructions
+///
+/// Each entry is a [`Command`](crate::flexspi::Command) variant, and a list of
+/// instructions in the [`sequence!`](crate::sequence) syntax. Variants with data,
+/// like `Custom`, take their data in parentheses.
+///
+/// The lookup table is always evaluated at compile time. If two entries share the same
+/// LUT index, or if any sequence has more than eight instructions, you'll observe a
+/// compile-time error.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::LookupTable;
+/// use imxrt_boot_gen::lut;
+///
+/// const LUT: LookupTable = lut! {
+///     Read => [CMD_SDR x1 0xEB, RADDR_SDR x4 24, DUMMY_SDR x4 6, READ_SDR x4 4],
+///     ReadStatus => [CMD_SDR x1 0x05, READ_SDR x1 4],
+///     WriteEnable => [CMD_SDR x1 0x06],
+///     Custom(6) => [CMD_SDR x1 0x31, WRITE_SDR x1 1],
+/// };
+/// ```
+#[macro_export]
+macro_rules! lut {
+    ($($command:ident $(($arg:expr))? => [$($instr:tt)*]),* $(,)?) => {{
+        const LOOKUP_TABLE: $crate::flexspi::LookupTable = {
+            $crate::flexspi::macros::assert_unique_commands(&[
+                $($crate::flexspi::Command::$command $(($arg))?),*
+            ]);
+            $crate::flexspi::LookupTable::new()
+                $(.command(
+                    $crate::flexspi::Command::$command $(($arg))?,
+                    $crate::sequence!($($instr)*),
+                ))*
+        };
+        LOOKUP_TABLE
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use crate::flexspi::{
+        opcodes::{ddr, sdr},
+        Command, Instr, LookupTable, NandCommand, Pads, SequenceBuilder,
+    };
+
+    #[test]
+    fn lut() {
+        const LUT: LookupTable = crate::lut! {
+            Read => [CMD_DDR x8 0xEE, CMD_DDR x8 0x11, RADDR_DDR x8 32, DUMMY_DDR x8 20, READ_DDR x8 4],
+            Nand(NandCommand::ReadPage) => [CMD_SDR x1 0x13, RADDR_SDR x1 24],
+        };
+        const EXPECTED: LookupTable = LookupTable::new()
+            .command(
+                Command::Read,
+                SequenceBuilder::new()
+                    .instr(Instr::new(ddr::CMD, Pads::Eight, 0xEE))
+                    .instr(Instr::new(ddr::CMD, Pads::Eight, 0x11))
+                    .instr(Instr::new(ddr::RADDR, Pads::Eight, 32))
+                    .instr(Instr::new(ddr::DUMMY, Pads::Eight, 20))
+                    .instr(Instr::new(ddr::READ, Pads::Eight, 4))
+                    .build(),
+            )
+            .command(
+                Command::Nand(NandCommand::ReadPage),
+                SequenceBuilder::new()
+                    .instr(Instr::new(sdr::CMD, Pads::One, 0x13))
+                    .instr(Instr::new(sdr::RADDR, Pads::One, 24))
+                    .build(),
+            );
+        assert_eq!(LUT, EXPECTED);
+        assert_eq!(crate::lut! {}, LookupTable::new());
+    }
+}
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::{flexspi::LookupTable, lut};
+/// const LUT: LookupTable = lut! {
+///     ExitNoCmd => [CMD_SDR x1 0xFF],
+///     RestoreNoCmd => [CMD_SDR x1 0xFF],
+/// };
+/// ```
+#[cfg(doctest)]
+struct LutUniqueCommands;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::{flexspi::LookupTable, lut};
+/// const LUT: LookupTable = lut! {
+///     ExitNoCmd => [CMD_SDR x1 0xFF],
+///     Dummy => [CMD_SDR x1 0xFF], // <------- THIS SHOULD FAIL
+/// };
+/// ```
+#[cfg(doctest)]
+struct LutDuplicateCommands;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::{flexspi::Sequence, sequence};
+/// let seq: Sequence = sequence![
+///     CMD_SDR x1 1, CMD_SDR x1 2, CMD_SDR x1 3, CMD_SDR x1 4,
+///     CMD_SDR x1 5, CMD_SDR x1 6, CMD_SDR x1 7, CMD_SDR x1 8,
+/// ];
+/// ```
+#[cfg(doctest)]
+struct SequenceMacroInstructionLimit;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::{flexspi::Sequence, sequence};
+/// let seq: Sequence = sequence![
+///     CMD_SDR x1 1, CMD_SDR x1 2, CMD_SDR x1 3, CMD_SDR x1 4,
+///     CMD_SDR x1 5, CMD_SDR x1 6, CMD_SDR x1 7, CMD_SDR x1 8,
+///     CMD_SDR x1 9, // <------- THIS SHOULD FAIL
+/// ];
+/// ```
+#[cfg(doctest)]
+struct SequenceMacroTooManyInstructions;
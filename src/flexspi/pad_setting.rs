@@ -0,0 +1,296 @@
+//! FlexSPI pad setting overrides
+//!
+//! A [`PadSetting`] is the IOMUXC pad configuration that the ROM applies to the
+//! FlexSPI pins when pad setting overrides are enabled. Use pad settings to tune
+//! drive strength and slew rate for high-speed devices. The fields of a
+//! `PadSetting` depend on your chip.
+//!
+//! See [`ConfigurationBlock::cs_pad_setting_override`](super::ConfigurationBlock::cs_pad_setting_override)
+//! and the related methods to apply a pad setting.
+
+#[cfg(not(feature = "imxrt500"))]
+pub use imxrt10xx::*;
+#[cfg(feature = "imxrt500")]
+pub use imxrt500::*;
+
+/// `SW_PAD_CTL` pad settings for i.MX RT 10xx chips
+#[cfg(not(feature = "imxrt500"))]
+mod imxrt10xx {
+    const SRE_SHIFT: u32 = 0;
+    const DSE_SHIFT: u32 = 3;
+    const SPEED_SHIFT: u32 = 6;
+    const ODE_SHIFT: u32 = 11;
+    const PKE_SHIFT: u32 = 12;
+    const PUE_SHIFT: u32 = 13;
+    const PUS_SHIFT: u32 = 14;
+    const HYS_SHIFT: u32 = 16;
+
+    /// Slew rate (`SRE`)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(u32)]
+    pub enum SlewRate {
+        Slow = 0,
+        Fast = 1,
+    }
+
+    /// Drive strength (`DSE`)
+    ///
+    /// `R0` is the drive strength's base resistance. `R0_2` is `R0/2`, and so on.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(u32)]
+    pub enum DriveStrength {
+        Disabled = 0,
+        R0 = 1,
+        R0_2 = 2,
+        R0_3 = 3,
+        R0_4 = 4,
+        R0_5 = 5,
+        R0_6 = 6,
+        R0_7 = 7,
+    }
+
+    /// Speed (`SPEED`)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(u32)]
+    pub enum Speed {
+        /// 50MHz
+        Low = 0,
+        /// 100MHz
+        Medium = 1,
+        /// 150MHz
+        Fast = 2,
+        /// 200MHz
+        Max = 3,
+    }
+
+    /// Pull up / pull down resistor (`PUS`)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(u32)]
+    pub enum Pull {
+        Down100k = 0,
+        Up47k = 1,
+        Up100k = 2,
+        Up22k = 3,
+    }
+
+    /// Pull / keep configuration (`PKE` and `PUE`)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PullKeep {
+        /// Pull and keeper are disabled
+        Disabled,
+        /// Use the keeper
+        Keeper,
+        /// Use the pull resistor
+        Pull(Pull),
+    }
+
+    /// A `SW_PAD_CTL` pad setting
+    ///
+    /// Unless otherwise specified, all fields are set to a bit pattern of zero.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::pad_setting::*;
+    ///
+    /// const PAD_SETTING: PadSetting = PadSetting::new()
+    ///     .slew_rate(SlewRate::Fast)
+    ///     .drive_strength(DriveStrength::R0_6)
+    ///     .speed(Speed::Max);
+    /// assert_eq!(PAD_SETTING.bits(), 0xF1);
+    /// ```
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[repr(transparent)]
+    pub struct PadSetting(u32);
+
+    impl PadSetting {
+        /// Create a new pad setting
+        pub const fn new() -> Self {
+            PadSetting(0)
+        }
+        /// Set the slew rate
+        pub const fn slew_rate(self, slew_rate: SlewRate) -> Self {
+            self.field(SRE_SHIFT, 0b1, slew_rate as u32)
+        }
+        /// Set the drive strength
+        pub const fn drive_strength(self, drive_strength: DriveStrength) -> Self {
+            self.field(DSE_SHIFT, 0b111, drive_strength as u32)
+        }
+        /// Set the speed
+        pub const fn speed(self, speed: Speed) -> Self {
+            self.field(SPEED_SHIFT, 0b11, speed as u32)
+        }
+        /// Enable or disable the open drain output
+        pub const fn open_drain(self, open_drain: bool) -> Self {
+            self.field(ODE_SHIFT, 0b1, open_drain as u32)
+        }
+        /// Set the pull / keep configuration
+        pub const fn pull_keep(self, pull_keep: PullKeep) -> Self {
+            match pull_keep {
+                PullKeep::Disabled => self.field(PKE_SHIFT, 0b1, 0),
+                PullKeep::Keeper => self.field(PKE_SHIFT, 0b1, 1).field(PUE_SHIFT, 0b1, 0),
+                PullKeep::Pull(pull) => self
+                    .field(PKE_SHIFT, 0b1, 1)
+                    .field(PUE_SHIFT, 0b1, 1)
+                    .field(PUS_SHIFT, 0b11, pull as u32),
+            }
+        }
+        /// Enable or disable the hysteresis
+        pub const fn hysteresis(self, hysteresis: bool) -> Self {
+            self.field(HYS_SHIFT, 0b1, hysteresis as u32)
+        }
+        /// Returns the raw `SW_PAD_CTL` value
+        pub const fn bits(self) -> u32 {
+            self.0
+        }
+        pub(crate) const fn from_bits(bits: u32) -> Self {
+            PadSetting(bits)
+        }
+        const fn field(self, shift: u32, mask: u32, value: u32) -> Self {
+            PadSetting((self.0 & !(mask << shift)) | (value << shift))
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn pull_keep() {
+            const PULL: PadSetting = PadSetting::new().pull_keep(PullKeep::Pull(Pull::Up22k));
+            assert_eq!(PULL.bits(), 0xF000);
+            const KEEPER: PadSetting = PULL.pull_keep(PullKeep::Keeper);
+            assert_eq!(KEEPER.bits(), 0xD000);
+        }
+
+        #[test]
+        fn all_fields() {
+            const PAD_SETTING: PadSetting = PadSetting::new()
+                .slew_rate(SlewRate::Fast)
+                .drive_strength(DriveStrength::R0_4)
+                .speed(Speed::Medium)
+                .open_drain(true)
+                .pull_keep(PullKeep::Pull(Pull::Up47k))
+                .hysteresis(true);
+            assert_eq!(PAD_SETTING.bits(), 0x1_7861);
+        }
+    }
+}
+
+/// `IOPCTL` pad settings for i.MX RT 500 chips
+#[cfg(feature = "imxrt500")]
+mod imxrt500 {
+    const FSEL_SHIFT: u32 = 0;
+    const PUPDENA_SHIFT: u32 = 4;
+    const PUPDSEL_SHIFT: u32 = 5;
+    const IBENA_SHIFT: u32 = 6;
+    const SLEWRATE_SHIFT: u32 = 7;
+    const FULLDRIVE_SHIFT: u32 = 8;
+    const ODENA_SHIFT: u32 = 10;
+
+    /// Slew rate (`SLEWRATE`)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(u32)]
+    pub enum SlewRate {
+        Normal = 0,
+        Slow = 1,
+    }
+
+    /// Pull up / pull down configuration (`PUPDENA` and `PUPDSEL`)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Pull {
+        Disabled,
+        Down,
+        Up,
+    }
+
+    /// An `IOPCTL` pad setting
+    ///
+    /// The override replaces the whole `IOPCTL` register, including the function
+    /// select. Unless otherwise specified, all fields are set to a bit pattern of zero.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::pad_setting::*;
+    ///
+    /// const PAD_SETTING: PadSetting = PadSetting::new()
+    ///     .function(1)
+    ///     .input_buffer(true)
+    ///     .full_drive(true);
+    /// assert_eq!(PAD_SETTING.bits(), 0x141);
+    /// ```
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    #[repr(transparent)]
+    pub struct PadSetting(u32);
+
+    impl PadSetting {
+        /// Create a new pad setting
+        pub const fn new() -> Self {
+            PadSetting(0)
+        }
+        /// Set the function select (`FSEL`)
+        ///
+        /// If `function` is larger than 15, you'll observe a compile-time error.
+        pub const fn function(self, function: u8) -> Self {
+            if function > 0xF {
+                panic!("The function select is four bits wide");
+            }
+            self.field(FSEL_SHIFT, 0xF, function as u32)
+        }
+        /// Set the pull up / pull down configuration
+        pub const fn pull(self, pull: Pull) -> Self {
+            match pull {
+                Pull::Disabled => self.field(PUPDENA_SHIFT, 0b1, 0),
+                Pull::Down => self
+                    .field(PUPDENA_SHIFT, 0b1, 1)
+                    .field(PUPDSEL_SHIFT, 0b1, 0),
+                Pull::Up => self
+                    .field(PUPDENA_SHIFT, 0b1, 1)
+                    .field(PUPDSEL_SHIFT, 0b1, 1),
+            }
+        }
+        /// Enable or disable the input buffer (`IBENA`)
+        pub const fn input_buffer(self, input_buffer: bool) -> Self {
+            self.field(IBENA_SHIFT, 0b1, input_buffer as u32)
+        }
+        /// Set the slew rate
+        pub const fn slew_rate(self, slew_rate: SlewRate) -> Self {
+            self.field(SLEWRATE_SHIFT, 0b1, slew_rate as u32)
+        }
+        /// Select full drive strength (`FULLDRIVE`)
+        ///
+        /// If not set, the pad uses normal drive strength.
+        pub const fn full_drive(self, full_drive: bool) -> Self {
+            self.field(FULLDRIVE_SHIFT, 0b1, full_drive as u32)
+        }
+        /// Enable or disable the open drain output (`ODENA`)
+        pub const fn open_drain(self, open_drain: bool) -> Self {
+            self.field(ODENA_SHIFT, 0b1, open_drain as u32)
+        }
+        /// Returns the raw `IOPCTL` value
+        pub const fn bits(self) -> u32 {
+            self.0
+        }
+        pub(crate) const fn from_bits(bits: u32) -> Self {
+            PadSetting(bits)
+        }
+        const fn field(self, shift: u32, mask: u32, value: u32) -> Self {
+            PadSetting((self.0 & !(mask << shift)) | (value << shift))
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn all_fields() {
+            const PAD_SETTING: PadSetting = PadSetting::new()
+                .function(1)
+                .pull(Pull::Up)
+                .input_buffer(true)
+                .slew_rate(SlewRate::Slow)
+                .full_drive(true)
+                .open_drain(true);
+            assert_eq!(PAD_SETTING.bits(), 0x5F1);
+            assert_eq!(PAD_SETTING.pull(Pull::Disabled).bits(), 0x5E1);
+        }
+    }
+}
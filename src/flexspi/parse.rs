@@ -0,0 +1,535 @@
+//! A text assembler for FlexSPI sequences
+//!
+//! The text format matches the [`Display`](core::fmt::Display) output of
+//! [`Instr`] and [`Sequence`], so that a disassembled sequence parses back
+//! into the same sequence.
+
+use core::fmt;
+use core::str::FromStr;
+
+use super::sequence::{Opcode, INSTRUCTIONS_PER_SEQUENCE};
+use super::{opcodes::ddr, opcodes::sdr, Instr, Pads, Sequence, STOP};
+
+/// Describes why a sequence failed to parse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The opcode name is unknown
+    UnknownOpcode,
+    /// The instruction has an opcode, but no pads
+    ExpectedPads,
+    /// The pads are not `1`, `2`, `4`, `8`, `SINGLE`, `DUAL`, `QUAD` or `OCTAL`
+    InvalidPads,
+    /// The instruction has an opcode and pads, but no operand
+    ExpectedOperand,
+    /// The operand is not a decimal or `0x` hex number, or is larger than `0xFF`
+    InvalidOperand,
+    /// The instruction has an unexpected token
+    ///
+    /// `bits` is only allowed after address operands, and `cycles` is only
+    /// allowed after dummy operands.
+    UnexpectedToken,
+    /// The sequence has more than eight instructions
+    TooManyInstructions,
+}
+
+impl ParseErrorKind {
+    /// Returns a message that describes the error
+    pub const fn message(self) -> &'static str {
+        match self {
+            ParseErrorKind::UnknownOpcode => "Unknown opcode",
+            ParseErrorKind::ExpectedPads => "Expected pads after the opcode",
+            ParseErrorKind::InvalidPads => "Pads must be 1, 2, 4, 8, SINGLE, DUAL, QUAD or OCTAL",
+            ParseErrorKind::ExpectedOperand => "Expected an operand after the pads",
+            ParseErrorKind::InvalidOperand => {
+                "The operand must be a decimal or hex number no larger than 0xFF"
+            }
+            ParseErrorKind::UnexpectedToken => "Unexpected token",
+            ParseErrorKind::TooManyInstructions => "A sequence has at most eight instructions",
+        }
+    }
+}
+
+/// An error when parsing a [`Sequence`]
+///
+/// Lines and columns start at 1. Columns count bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Returns the line of the token that caused the error
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+    /// Returns the column of the token that caused the error
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+    /// Returns the kind of error
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line,
+            self.column,
+            self.kind.message()
+        )
+    }
+}
+
+/// The most tokens in one instruction, plus one to detect extra tokens
+const MAX_TOKENS: usize = 5;
+
+/// A token's start and end, as byte offsets
+#[derive(Clone, Copy)]
+struct Token {
+    start: usize,
+    end: usize,
+}
+
+const fn error(kind: ParseErrorKind, line: usize, line_start: usize, token: Token) -> ParseError {
+    ParseError {
+        line,
+        column: token.start - line_start + 1,
+        kind,
+    }
+}
+
+const fn is_space(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\r')
+}
+
+const fn is_separator(byte: u8) -> bool {
+    matches!(byte, b';' | b'\n')
+}
+
+/// Returns `true` if the token equals `expected`
+const fn token_eq(bytes: &[u8], token: Token, expected: &str) -> bool {
+    let expected = expected.as_bytes();
+    if token.end - token.start != expected.len() {
+        return false;
+    }
+    let mut idx = 0;
+    while idx < expected.len() {
+        if bytes[token.start + idx] != expected[idx] {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+/// Parse a decimal or `0x` hex number that's no larger than `max`
+const fn parse_number(bytes: &[u8], token: Token, max: u32) -> Option<u32> {
+    let mut idx = token.start;
+    let mut radix = 10;
+    if token.end - token.start > 2
+        && bytes[idx] == b'0'
+        && (bytes[idx + 1] == b'x' || bytes[idx + 1] == b'X')
+    {
+        radix = 16;
+        idx += 2;
+    }
+    if idx == token.end {
+        return None;
+    }
+    let mut value: u32 = 0;
+    while idx < token.end {
+        let digit = match bytes[idx] {
+            byte @ b'0'..=b'9' => byte - b'0',
+            byte @ b'a'..=b'f' if radix == 16 => byte - b'a' + 10,
+            byte @ b'A'..=b'F' if radix == 16 => byte - b'A' + 10,
+            _ => return None,
+        };
+        value = value * radix + digit as u32;
+        if value > max {
+            return None;
+        }
+        idx += 1;
+    }
+    Some(value)
+}
+
+/// Parse an opcode name, including the `UNKNOWN(0x..)` names from the disassembler
+const fn parse_opcode(bytes: &[u8], token: Token) -> Option<Opcode> {
+    let mut raw = 0;
+    while raw <= 0x3F {
+        let opcode = Opcode::from_raw(raw);
+        if let Some(name) = opcode.name() {
+            if token_eq(bytes, token, name) {
+                return Some(opcode);
+            }
+        }
+        raw += 1;
+    }
+    const UNKNOWN: &[u8] = b"UNKNOWN(";
+    let len = token.end - token.start;
+    if len <= UNKNOWN.len() + 1 || bytes[token.end - 1] != b')' {
+        return None;
+    }
+    let mut idx = 0;
+    while idx < UNKNOWN.len() {
+        if bytes[token.start + idx] != UNKNOWN[idx] {
+            return None;
+        }
+        idx += 1;
+    }
+    let number = Token {
+        start: token.start + UNKNOWN.len(),
+        end: token.end - 1,
+    };
+    match parse_number(bytes, number, 0x3F) {
+        Some(raw) => Some(Opcode::from_raw(raw as u8)),
+        None => None,
+    }
+}
+
+const fn parse_pads(bytes: &[u8], token: Token) -> Option<Pads> {
+    if token_eq(bytes, token, "1") || token_eq(bytes, token, "SINGLE") {
+        Some(Pads::One)
+    } else if token_eq(bytes, token, "2") || token_eq(bytes, token, "DUAL") {
+        Some(Pads::Two)
+    } else if token_eq(bytes, token, "4") || token_eq(bytes, token, "QUAD") {
+        Some(Pads::Four)
+    } else if token_eq(bytes, token, "8") || token_eq(bytes, token, "OCTAL") {
+        Some(Pads::Eight)
+    } else {
+        None
+    }
+}
+
+/// Parse the trailing `STOP` marker, `(xN)`, from the disassembler
+const fn parse_repeat(bytes: &[u8], token: Token) -> Option<usize> {
+    if token.end - token.start < 4
+        || bytes[token.start] != b'('
+        || bytes[token.start + 1] != b'x'
+        || bytes[token.end - 1] != b')'
+    {
+        return None;
+    }
+    let number = Token {
+        start: token.start + 2,
+        end: token.end - 1,
+    };
+    match parse_number(bytes, number, INSTRUCTIONS_PER_SEQUENCE as u32) {
+        Some(0) | None => None,
+        Some(count) => Some(count as usize),
+    }
+}
+
+impl Sequence {
+    /// Parse a sequence from text
+    ///
+    /// Separate instructions with `;` or newlines. Each instruction is an opcode,
+    /// pads and an operand, separated by whitespace:
+    ///
+    /// - Opcode names match the [`Display`](core::fmt::Display) names, like
+    ///   `CMD_SDR`, `RADDR_DDR` and `JUMP_ON_CS`.
+    /// - Pads are `1`, `2`, `4` or `8`, or `SINGLE`, `DUAL`, `QUAD` or `OCTAL`.
+    /// - Operands are decimal, or hex with a `0x` prefix. Address operands may be
+    ///   followed by `bits`, and dummy operands may be followed by `cycles`.
+    ///
+    /// `STOP` needs no pads or operand. Any unspecified instructions are set to
+    /// [`STOP`], just like [`SequenceBuilder`](crate::flexspi::SequenceBuilder).
+    /// The output of the sequence's `Display` implementation parses back into the
+    /// same sequence.
+    ///
+    /// `parse` is a `const fn`, so you can check a sequence at compile time:
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{Instr, Pads, Sequence, SequenceBuilder, opcodes::sdr::*};
+    ///
+    /// const SEQ_READ: Sequence =
+    ///     match Sequence::parse("CMD_SDR 1 0xEB; RADDR_SDR 4 0x18; DUMMY_SDR 4 6; READ_SDR 4 4") {
+    ///         Ok(seq) => seq,
+    ///         Err(err) => panic!("{}", err.kind().message()),
+    ///     };
+    ///
+    /// assert_eq!(
+    ///     SEQ_READ,
+    ///     SequenceBuilder::new()
+    ///         .instr(Instr::new(CMD, Pads::One, 0xEB))
+    ///         .instr(Instr::new(RADDR, Pads::Four, 0x18))
+    ///         .instr(Instr::new(DUMMY, Pads::Four, 6))
+    ///         .instr(Instr::new(READ, Pads::Four, 4))
+    ///         .build()
+    /// );
+    /// ```
+    pub const fn parse(text: &str) -> Result<Sequence, ParseError> {
+        let bytes = text.as_bytes();
+        let mut sequence = Sequence::stopped();
+        let mut count = 0;
+        let mut pos = 0;
+        let mut line = 1;
+        let mut line_start = 0;
+        while pos <= bytes.len() {
+            // Tokenize one instruction
+            let mut tokens = [Token { start: 0, end: 0 }; MAX_TOKENS];
+            let mut num_tokens = 0;
+            while pos < bytes.len() && !is_separator(bytes[pos]) {
+                if is_space(bytes[pos]) {
+                    pos += 1;
+                    continue;
+                }
+                let start = pos;
+                while pos < bytes.len() && !is_separator(bytes[pos]) && !is_space(bytes[pos]) {
+                    pos += 1;
+                }
+                if num_tokens < MAX_TOKENS {
+                    tokens[num_tokens] = Token { start, end: pos };
+                    num_tokens += 1;
+                }
+            }
+            if num_tokens > 0 {
+                let opcode = match parse_opcode(bytes, tokens[0]) {
+                    Some(opcode) => opcode,
+                    None => {
+                        return Err(error(
+                            ParseErrorKind::UnknownOpcode,
+                            line,
+                            line_start,
+                            tokens[0],
+                        ))
+                    }
+                };
+                // The STOP instruction, optionally with the disassembler's repeat marker
+                let mut stops = 0;
+                if opcode.is(STOP.opcode()) && num_tokens == 1 {
+                    stops = 1;
+                } else if opcode.is(STOP.opcode()) && num_tokens == 2 {
+                    stops = match parse_repeat(bytes, tokens[1]) {
+                        Some(stops) => stops,
+                        None => {
+                            return Err(error(
+                                ParseErrorKind::UnexpectedToken,
+                                line,
+                                line_start,
+                                tokens[1],
+                            ))
+                        }
+                    };
+                }
+                if stops > 0 {
+                    if count + stops > INSTRUCTIONS_PER_SEQUENCE {
+                        return Err(error(
+                            ParseErrorKind::TooManyInstructions,
+                            line,
+                            line_start,
+                            tokens[0],
+                        ));
+                    }
+                    // The sequence is already filled with STOPs
+                    count += stops;
+                } else {
+                    if num_tokens < 2 {
+                        return Err(error(
+                            ParseErrorKind::ExpectedPads,
+                            line,
+                            line_start,
+                            tokens[0],
+                        ));
+                    }
+                    let pads = match parse_pads(bytes, tokens[1]) {
+                        Some(pads) => pads,
+                        None => {
+                            return Err(error(
+                                ParseErrorKind::InvalidPads,
+                                line,
+                                line_start,
+                                tokens[1],
+                            ))
+                        }
+                    };
+                    if num_tokens < 3 {
+                        return Err(error(
+                            ParseErrorKind::ExpectedOperand,
+                            line,
+                            line_start,
+                            tokens[1],
+                        ));
+                    }
+                    let operand = match parse_number(bytes, tokens[2], 0xFF) {
+                        Some(operand) => operand as u8,
+                        None => {
+                            return Err(error(
+                                ParseErrorKind::InvalidOperand,
+                                line,
+                                line_start,
+                                tokens[2],
+                            ))
+                        }
+                    };
+                    if num_tokens > 3 {
+                        let is_address = opcode.is(sdr::RADDR)
+                            || opcode.is(sdr::CADDR)
+                            || opcode.is(ddr::RADDR)
+                            || opcode.is(ddr::CADDR);
+                        let is_dummy = opcode.is(sdr::DUMMY)
+                            || opcode.is(sdr::DUMMY_RWDS)
+                            || opcode.is(ddr::DUMMY)
+                            || opcode.is(ddr::DUMMY_RWDS);
+                        let suffix = (is_address && token_eq(bytes, tokens[3], "bits"))
+                            || (is_dummy && token_eq(bytes, tokens[3], "cycles"));
+                        if !suffix {
+                            return Err(error(
+                                ParseErrorKind::UnexpectedToken,
+                                line,
+                                line_start,
+                                tokens[3],
+                            ));
+                        }
+                    }
+                    if num_tokens > 4 {
+                        return Err(error(
+                            ParseErrorKind::UnexpectedToken,
+                            line,
+                            line_start,
+                            tokens[4],
+                        ));
+                    }
+                    if count >= INSTRUCTIONS_PER_SEQUENCE {
+                        return Err(error(
+                            ParseErrorKind::TooManyInstructions,
+                            line,
+                            line_start,
+                            tokens[0],
+                        ));
+                    }
+                    sequence.0[count] = Instr::new(opcode, pads, operand);
+                    count += 1;
+                }
+            }
+
+            // Skip the separator
+            if pos < bytes.len() && bytes[pos] == b'\n' {
+                line += 1;
+                line_start = pos + 1;
+            }
+            pos += 1;
+        }
+        Ok(sequence)
+    }
+}
+
+impl FromStr for Sequence {
+    type Err = ParseError;
+    fn from_str(text: &str) -> Result<Self, ParseError> {
+        Sequence::parse(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ParseError, ParseErrorKind};
+    use crate::flexspi::{
+        opcodes::{ddr, sdr},
+        Instr, Pads, Sequence, SequenceBuilder, JUMP_ON_CS, STOP,
+    };
+
+    fn error(line: usize, column: usize, kind: ParseErrorKind) -> Result<Sequence, ParseError> {
+        Err(ParseError { line, column, kind })
+    }
+
+    #[test]
+    fn parse() {
+        const EXPECTED: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(sdr::CMD, Pads::One, 0xEB))
+            .instr(Instr::new(sdr::RADDR, Pads::Four, 0x18))
+            .instr(Instr::new(sdr::DUMMY, Pads::Four, 6))
+            .instr(Instr::new(sdr::READ, Pads::Four, 4))
+            .build();
+        assert_eq!(
+            Sequence::parse("CMD_SDR 1 0xEB; RADDR_SDR 4 0x18; DUMMY_SDR 4 6; READ_SDR 4 4"),
+            Ok(EXPECTED)
+        );
+        assert_eq!(
+            "\n  CMD_SDR SINGLE 0xeb\r\n\tRADDR_SDR QUAD 24 bits\nDUMMY_SDR QUAD 6 cycles;;\nREAD_SDR QUAD 0x04;\n"
+                .parse::<Sequence>(),
+            Ok(EXPECTED)
+        );
+        assert_eq!(Sequence::parse(""), Ok(Sequence::stopped()));
+    }
+
+    #[test]
+    fn round_trip() {
+        let sequences = [
+            SequenceBuilder::new()
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0xEE))
+                .instr(Instr::new(ddr::CMD, Pads::Eight, 0x11))
+                .instr(Instr::new(ddr::RADDR, Pads::Eight, 32))
+                .instr(Instr::new(ddr::DUMMY_RWDS, Pads::Eight, 20))
+                .instr(Instr::new(ddr::READ, Pads::Eight, 4))
+                .instr(JUMP_ON_CS)
+                .build(),
+            SequenceBuilder::new()
+                .instr(Instr::new(sdr::CMD, Pads::One, 0x06))
+                .instr(STOP)
+                .instr(Instr::from_raw(0x5612))
+                .instr(Instr::from_raw(0x0105))
+                .build(),
+            Sequence::stopped(),
+        ];
+        for sequence in &sequences {
+            assert_eq!(Sequence::parse(&sequence.to_string()), Ok(*sequence));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Sequence::parse("CMD_SDR 1 0x06\n  CMD 1 0x06"),
+            error(2, 3, ParseErrorKind::UnknownOpcode)
+        );
+        assert_eq!(
+            Sequence::parse("CMD_SDR 1 0x06; CMD_SDR"),
+            error(1, 17, ParseErrorKind::ExpectedPads)
+        );
+        assert_eq!(
+            Sequence::parse("CMD_SDR 3 0x06"),
+            error(1, 9, ParseErrorKind::InvalidPads)
+        );
+        assert_eq!(
+            Sequence::parse("CMD_SDR 1"),
+            error(1, 9, ParseErrorKind::ExpectedOperand)
+        );
+        assert_eq!(
+            Sequence::parse("CMD_SDR 1 0x100"),
+            error(1, 11, ParseErrorKind::InvalidOperand)
+        );
+        assert_eq!(
+            Sequence::parse("CMD_SDR 1 6 cycles"),
+            error(1, 13, ParseErrorKind::UnexpectedToken)
+        );
+        assert_eq!(
+            Sequence::parse("RADDR_SDR 1 24 bits 1"),
+            error(1, 21, ParseErrorKind::UnexpectedToken)
+        );
+        assert_eq!(
+            Sequence::parse("CMD_SDR 1 1; STOP (x7); CMD_SDR 1 2"),
+            error(1, 25, ParseErrorKind::TooManyInstructions)
+        );
+        assert_eq!(
+            Sequence::parse("STOP (x0)"),
+            error(1, 6, ParseErrorKind::UnexpectedToken)
+        );
+    }
+
+    #[test]
+    fn display() {
+        let err = Sequence::parse("READ_SDR 4").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 10: Expected an operand after the pads"
+        );
+    }
+}
@@ -5,6 +5,9 @@
 
 use core::fmt;
 
+use super::fields::DeviceModeSequence;
+use super::lookup::NUMBER_OF_SEQUENCES;
+
 pub(crate) const INSTRUCTION_SIZE: usize = 2;
 
 /// A FlexSPI instruction
@@ -13,7 +16,7 @@ pub(crate) const INSTRUCTION_SIZE: usize = 2;
 /// Opcodes are available in the [`opcode` module](opcodes/index.html).
 ///
 /// `Instr`s are used to create FlexSPI lookup table command [`Sequence`s](struct.Sequence.html).
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct Instr([u8; INSTRUCTION_SIZE]);
 
@@ -27,12 +30,88 @@ impl Instr {
         Instr([operand, (opcode.0 << 2) | (pads as u8)])
     }
 
+    /// Create an instruction from its raw, 16 bit LUT representation
+    ///
+    /// Bits 15:10 are the opcode, bits 9:8 are the pads, and bits 7:0 are the operand.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{Instr, Pads, opcodes::sdr::*};
+    ///
+    /// let instr = Instr::from_raw(0x0A18);
+    /// assert_eq!(instr, Instr::new(RADDR, Pads::Four, 0x18));
+    /// assert_eq!(instr.opcode(), RADDR);
+    /// assert_eq!(instr.pads(), Pads::Four);
+    /// assert_eq!(instr.operand(), 0x18);
+    /// ```
+    pub const fn from_raw(raw: u16) -> Self {
+        Instr(raw.to_le_bytes())
+    }
+
+    /// Returns the instruction's opcode
+    pub const fn opcode(self) -> Opcode {
+        Opcode(self.0[1] >> 2)
+    }
+
+    /// Returns the number of pads used by the instruction
+    pub const fn pads(self) -> Pads {
+        Pads::from_raw(self.0[1])
+    }
+
+    /// Returns the instruction's operand
+    pub const fn operand(self) -> u8 {
+        self.0[0]
+    }
+
     const fn stop() -> Self {
         Instr::new(opcodes::STOP, Pads::One /* unused */, 0)
     }
 
-    const fn jump_on_cs() -> Self {
-        Instr::new(opcodes::JUMP_ON_CS, Pads::One /* unused */, 0)
+    /// Create a `JUMP_ON_CS` instruction that resumes at instruction `index`
+    ///
+    /// The FlexSPI controller stops the sequence, deasserts CS, and starts the next
+    /// sequence at `index` instead of at zero. Use this for XIP continuous read mode;
+    /// see [`ContinuousRead`](crate::flexspi::ContinuousRead).
+    ///
+    /// If `index` is not less than eight, you'll observe a compile-time error.
+    /// [`JUMP_ON_CS`](constant.JUMP_ON_CS.html) is the same as `jump_on_cs_to(0)`.
+    pub const fn jump_on_cs_to(index: u8) -> Self {
+        if index as usize >= INSTRUCTIONS_PER_SEQUENCE {
+            panic!("The JUMP_ON_CS index must point into the sequence");
+        }
+        Instr::new(opcodes::JUMP_ON_CS, Pads::One /* unused */, index)
+    }
+}
+
+/// Displays the instruction as `OPCODE PADS OPERAND`
+///
+/// Address operands are shown as a number of bits, and dummy operands are shown
+/// as a number of cycles. All other operands are shown in hex. Unknown opcodes are
+/// shown as `UNKNOWN(0x..)`.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{Instr, Pads, STOP, opcodes::sdr::*};
+///
+/// assert_eq!(Instr::new(RADDR, Pads::Four, 24).to_string(), "RADDR_SDR QUAD 24 bits");
+/// assert_eq!(Instr::new(CMD, Pads::One, 0xEB).to_string(), "CMD_SDR SINGLE 0xEB");
+/// assert_eq!(STOP.to_string(), "STOP");
+/// ```
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use opcodes::{ddr, sdr};
+        if *self == STOP {
+            return write!(f, "STOP");
+        }
+        let opcode = self.opcode();
+        write!(f, "{} {} ", opcode, self.pads())?;
+        match opcode {
+            sdr::RADDR | sdr::CADDR | ddr::RADDR | ddr::CADDR => {
+                write!(f, "{} bits", self.operand())
+            }
+            sdr::DUMMY | sdr::DUMMY_RWDS | ddr::DUMMY | ddr::DUMMY_RWDS => {
+                write!(f, "{} cycles", self.operand())
+            }
+            _ => write!(f, "{:#04X}", self.operand()),
+        }
     }
 }
 
@@ -43,10 +122,188 @@ impl fmt::Debug for Instr {
     }
 }
 
+/// Typed instruction constructors
+///
+/// Each constructor selects the opcode, and interprets the operand for that opcode.
+/// The `_ddr` variants select the DDR opcode. If an operand is out of range, you'll
+/// observe a compile-time error.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{AddressBits, Instr, Pads, opcodes::sdr::*};
+///
+/// assert_eq!(Instr::cmd(Pads::One, 0xEB), Instr::new(CMD, Pads::One, 0xEB));
+/// assert_eq!(Instr::raddr(Pads::Four, AddressBits::B24), Instr::new(RADDR, Pads::Four, 24));
+/// assert_eq!(Instr::dummy(Pads::Four, 6), Instr::new(DUMMY, Pads::Four, 6));
+/// assert_eq!(Instr::read(Pads::Four), Instr::new(READ, Pads::Four, 0x04));
+/// ```
+impl Instr {
+    /// Transmit the command code `opcode` to flash
+    pub const fn cmd(pads: Pads, opcode: u8) -> Self {
+        Instr::new(opcodes::sdr::CMD, pads, opcode)
+    }
+    /// Transmit the command code `opcode` to flash, using DDR
+    pub const fn cmd_ddr(pads: Pads, opcode: u8) -> Self {
+        Instr::new(opcodes::ddr::CMD, pads, opcode)
+    }
+    /// Transmit a row address of `bits` to flash
+    pub const fn raddr(pads: Pads, bits: AddressBits) -> Self {
+        Instr::new(opcodes::sdr::RADDR, pads, bits as u8)
+    }
+    /// Transmit a row address of `bits` to flash, using DDR
+    pub const fn raddr_ddr(pads: Pads, bits: AddressBits) -> Self {
+        Instr::new(opcodes::ddr::RADDR, pads, bits as u8)
+    }
+    /// Transmit a column address of `bits` to flash
+    ///
+    /// `bits` must be between 1 and 32.
+    pub const fn caddr(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::sdr::CADDR, pads, column_address_bits(bits))
+    }
+    /// Transmit a column address of `bits` to flash, using DDR
+    ///
+    /// `bits` must be between 1 and 32.
+    pub const fn caddr_ddr(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::ddr::CADDR, pads, column_address_bits(bits))
+    }
+    /// Transmit one mode bit to flash
+    ///
+    /// `bits` must be 0 or 1.
+    pub const fn mode1(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::sdr::MODE1, pads, mode_bits(bits, 1))
+    }
+    /// Transmit one mode bit to flash, using DDR
+    ///
+    /// `bits` must be 0 or 1.
+    pub const fn mode1_ddr(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::ddr::MODE1, pads, mode_bits(bits, 1))
+    }
+    /// Transmit two mode bits to flash
+    ///
+    /// `bits` must be less than 4.
+    pub const fn mode2(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::sdr::MODE2, pads, mode_bits(bits, 2))
+    }
+    /// Transmit two mode bits to flash, using DDR
+    ///
+    /// `bits` must be less than 4.
+    pub const fn mode2_ddr(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::ddr::MODE2, pads, mode_bits(bits, 2))
+    }
+    /// Transmit four mode bits to flash
+    ///
+    /// `bits` must be less than 16.
+    pub const fn mode4(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::sdr::MODE4, pads, mode_bits(bits, 4))
+    }
+    /// Transmit four mode bits to flash, using DDR
+    ///
+    /// `bits` must be less than 16.
+    pub const fn mode4_ddr(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::ddr::MODE4, pads, mode_bits(bits, 4))
+    }
+    /// Transmit eight mode bits to flash
+    pub const fn mode8(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::sdr::MODE8, pads, bits)
+    }
+    /// Transmit eight mode bits to flash, using DDR
+    pub const fn mode8_ddr(pads: Pads, bits: u8) -> Self {
+        Instr::new(opcodes::ddr::MODE8, pads, bits)
+    }
+    /// Transmit programming data to flash
+    ///
+    /// The operand is set to `0x04`, the value used in the reference lookup tables.
+    /// The FlexSPI controller takes the data size from the transfer.
+    pub const fn write(pads: Pads) -> Self {
+        Instr::new(opcodes::sdr::WRITE, pads, DATA_SIZE_HINT)
+    }
+    /// Transmit programming data to flash, using DDR
+    ///
+    /// See [`write`](Instr::write) for the operand.
+    pub const fn write_ddr(pads: Pads) -> Self {
+        Instr::new(opcodes::ddr::WRITE, pads, DATA_SIZE_HINT)
+    }
+    /// Receive data from flash
+    ///
+    /// The operand is set to `0x04`, the value used in the reference lookup tables.
+    /// The FlexSPI controller takes the data size from the transfer.
+    pub const fn read(pads: Pads) -> Self {
+        Instr::new(opcodes::sdr::READ, pads, DATA_SIZE_HINT)
+    }
+    /// Receive data from flash, using DDR
+    ///
+    /// See [`read`](Instr::read) for the operand.
+    pub const fn read_ddr(pads: Pads) -> Self {
+        Instr::new(opcodes::ddr::READ, pads, DATA_SIZE_HINT)
+    }
+    /// Leave the data lines undriven for `cycles` dummy cycles
+    ///
+    /// `cycles` must be non-zero.
+    pub const fn dummy(pads: Pads, cycles: u8) -> Self {
+        Instr::new(opcodes::sdr::DUMMY, pads, dummy_cycles(cycles))
+    }
+    /// Leave the data lines undriven for `cycles` dummy cycles, using DDR
+    ///
+    /// `cycles` must be non-zero.
+    pub const fn dummy_ddr(pads: Pads, cycles: u8) -> Self {
+        Instr::new(opcodes::ddr::DUMMY, pads, dummy_cycles(cycles))
+    }
+    /// Leave the data lines undriven for `cycles` dummy cycles, as signaled by RWDS
+    ///
+    /// `cycles` must be non-zero.
+    pub const fn dummy_rwds(pads: Pads, cycles: u8) -> Self {
+        Instr::new(opcodes::sdr::DUMMY_RWDS, pads, dummy_cycles(cycles))
+    }
+    /// Leave the data lines undriven for `cycles` dummy cycles, as signaled by RWDS,
+    /// using DDR
+    ///
+    /// `cycles` must be non-zero.
+    pub const fn dummy_rwds_ddr(pads: Pads, cycles: u8) -> Self {
+        Instr::new(opcodes::ddr::DUMMY_RWDS, pads, dummy_cycles(cycles))
+    }
+}
+
+/// The `READ` and `WRITE` operand used by the typed constructors
+const DATA_SIZE_HINT: u8 = 0x04;
+
+const fn column_address_bits(bits: u8) -> u8 {
+    if bits == 0 || bits > 32 {
+        panic!("The column address must be between 1 and 32 bits");
+    }
+    bits
+}
+
+const fn mode_bits(bits: u8, width: u32) -> u8 {
+    if bits >> width != 0 {
+        panic!("The mode bits do not fit in the mode instruction");
+    }
+    bits
+}
+
+const fn dummy_cycles(cycles: u8) -> u8 {
+    if cycles == 0 {
+        panic!("A dummy instruction needs at least one cycle");
+    }
+    cycles
+}
+
+/// The number of row address bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AddressBits {
+    /// 8 bit row address
+    B8 = 8,
+    /// 16 bit row address
+    B16 = 16,
+    /// 24 bit (3 byte) row address
+    B24 = 24,
+    /// 32 bit (4 byte) row address
+    B32 = 32,
+}
+
 /// STOP FlexSPI instruction
 pub const STOP: Instr = Instr::stop();
 /// JUMP_ON_CS FlexSPI instruction
-pub const JUMP_ON_CS: Instr = Instr::jump_on_cs();
+pub const JUMP_ON_CS: Instr = Instr::jump_on_cs_to(0);
 
 pub(crate) const INSTRUCTIONS_PER_SEQUENCE: usize = 8;
 
@@ -57,7 +314,7 @@ pub(crate) const INSTRUCTIONS_PER_SEQUENCE: usize = 8;
 /// you're interacting with.
 ///
 /// `Sequence`s are used to create a [`LookupTable`](crate::flexspi::LookupTable).
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct Sequence(pub(crate) [Instr; INSTRUCTIONS_PER_SEQUENCE]);
 pub(crate) const SEQUENCE_SIZE: usize = INSTRUCTIONS_PER_SEQUENCE * INSTRUCTION_SIZE;
@@ -66,6 +323,174 @@ impl Sequence {
     pub(crate) const fn stopped() -> Self {
         Sequence([STOP; INSTRUCTIONS_PER_SEQUENCE])
     }
+
+    /// Returns `true` if every instruction in the sequence is a [`STOP`]
+    pub(crate) const fn is_stopped(&self) -> bool {
+        let mut idx = 0;
+        while idx < INSTRUCTIONS_PER_SEQUENCE {
+            let instr = self.0[idx].0;
+            if instr[0] != STOP.0[0] || instr[1] != STOP.0[1] {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
+
+    /// Returns all eight instructions of the sequence, including any `STOP`s
+    pub const fn instructions(&self) -> &[Instr; INSTRUCTIONS_PER_SEQUENCE] {
+        &self.0
+    }
+
+    /// Returns an iterator over the sequence's instructions
+    ///
+    /// The iterator stops at the first [`STOP`], since the FlexSPI controller never
+    /// executes the instructions that follow a `STOP`.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{Instr, Pads, SequenceBuilder, opcodes::sdr::*};
+    ///
+    /// let seq = SequenceBuilder::new()
+    ///     .instr(Instr::new(CMD, Pads::One, 0x05))
+    ///     .instr(Instr::new(READ, Pads::One, 0x04))
+    ///     .build();
+    /// assert_eq!(seq.iter().count(), 2);
+    /// ```
+    pub const fn iter(&self) -> Instructions<'_> {
+        Instructions {
+            sequence: self,
+            idx: 0,
+        }
+    }
+
+    /// Returns the little-endian memory representation of the sequence
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{Instr, Pads, SequenceBuilder, opcodes::sdr::*};
+    ///
+    /// let seq = SequenceBuilder::new()
+    ///     .instr(Instr::new(CMD, Pads::One, 0x06))
+    ///     .build();
+    /// assert_eq!(seq.to_bytes()[..4], [0x06, 0x04, 0x00, 0x00]);
+    /// ```
+    pub const fn to_bytes(&self) -> [u8; SEQUENCE_SIZE] {
+        self.encode([0; SEQUENCE_SIZE], 0)
+    }
+
+    /// Encode the sequence into `bytes`, starting at `offset`
+    pub(crate) const fn encode<const N: usize>(
+        &self,
+        mut bytes: [u8; N],
+        offset: usize,
+    ) -> [u8; N] {
+        let mut idx = 0;
+        while idx < INSTRUCTIONS_PER_SEQUENCE {
+            let instr = offset + idx * INSTRUCTION_SIZE;
+            bytes[instr] = self.0[idx].0[0];
+            bytes[instr + 1] = self.0[idx].0[1];
+            idx += 1;
+        }
+        bytes
+    }
+
+    /// Decode a sequence from the `SEQUENCE_SIZE` bytes found at `offset`
+    pub(crate) const fn decode(bytes: &[u8], offset: usize) -> Self {
+        let mut seq = Sequence::stopped();
+        let mut idx = 0;
+        while idx < INSTRUCTIONS_PER_SEQUENCE {
+            let instr = offset + idx * INSTRUCTION_SIZE;
+            seq.0[idx] = Instr([bytes[instr], bytes[instr + 1]]);
+            idx += 1;
+        }
+        seq
+    }
+}
+
+/// Displays one instruction per line
+///
+/// Trailing `STOP`s are collapsed into a single line, like `STOP (x4)`.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{Instr, Pads, SequenceBuilder, opcodes::sdr::*};
+///
+/// let seq = SequenceBuilder::new()
+///     .instr(Instr::new(CMD, Pads::One, 0xEB))
+///     .instr(Instr::new(RADDR, Pads::Four, 24))
+///     .instr(Instr::new(DUMMY, Pads::Four, 6))
+///     .instr(Instr::new(READ, Pads::Four, 0x04))
+///     .build();
+/// assert_eq!(
+///     seq.to_string(),
+///     "CMD_SDR SINGLE 0xEB\n\
+///      RADDR_SDR QUAD 24 bits\n\
+///      DUMMY_SDR QUAD 6 cycles\n\
+///      READ_SDR QUAD 0x04\n\
+///      STOP (x4)"
+/// );
+/// ```
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_lines(f, "\n")
+    }
+}
+
+impl Sequence {
+    /// Write one instruction per line, with `separator` between lines
+    pub(crate) fn fmt_lines(&self, f: &mut fmt::Formatter, separator: &str) -> fmt::Result {
+        let trailing = self
+            .0
+            .iter()
+            .rev()
+            .take_while(|instr| **instr == STOP)
+            .count();
+        let executed = INSTRUCTIONS_PER_SEQUENCE - trailing;
+        for (idx, instr) in self.0[..executed].iter().enumerate() {
+            if idx > 0 {
+                f.write_str(separator)?;
+            }
+            write!(f, "{}", instr)?;
+        }
+        if trailing > 0 {
+            if executed > 0 {
+                f.write_str(separator)?;
+            }
+            write!(f, "{}", STOP)?;
+            if trailing > 1 {
+                write!(f, " (x{})", trailing)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Sequence {
+    type Item = Instr;
+    type IntoIter = Instructions<'a>;
+    fn into_iter(self) -> Instructions<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the instructions of a [`Sequence`]
+///
+/// Use [`Sequence::iter`] to create the iterator.
+#[derive(Debug, Clone)]
+pub struct Instructions<'a> {
+    sequence: &'a Sequence,
+    idx: usize,
+}
+
+impl Iterator for Instructions<'_> {
+    type Item = Instr;
+    fn next(&mut self) -> Option<Instr> {
+        let instr = *self.sequence.0.get(self.idx)?;
+        if instr == STOP {
+            self.idx = INSTRUCTIONS_PER_SEQUENCE;
+            return None;
+        }
+        self.idx += 1;
+        Some(instr)
+    }
 }
 
 /// A [`Sequence`] builder
@@ -73,7 +498,8 @@ impl Sequence {
 /// Use `SequenceBuilder` to define a FlexSPI LUT sequence. If you insert too many instructions
 /// into the sequence, you'll observe a compile-time error.
 ///
-/// Any unspecified instructions are set to [`STOP`].
+/// Any unspecified instructions are set to [`STOP`]. If you need more than eight
+/// instructions, use a [`MultiSequenceBuilder`].
 ///
 /// # Example
 ///
@@ -100,6 +526,7 @@ impl SequenceBuilder {
     /// Creates a new `SequenceBuilder` than can accept up to eight instructions
     ///
     /// All unspecified instructions are set to [`STOP`].
+    #[allow(clippy::new_without_default)]
     pub const fn new() -> Self {
         SequenceBuilder {
             sequence: Sequence::stopped(),
@@ -123,14 +550,154 @@ impl SequenceBuilder {
     }
 }
 
+/// The maximum number of instructions in a [`MultiSequence`]
+const MAX_MULTI_SEQUENCE_INSTRUCTIONS: usize = NUMBER_OF_SEQUENCES * INSTRUCTIONS_PER_SEQUENCE;
+
+/// A command that spans consecutive LUT sequences
+///
+/// The ROM executes a device mode configuration command, or a configuration command,
+/// across `number_of_luts` consecutive sequences. Use a `MultiSequence` when those
+/// commands need more than eight instructions. Create a `MultiSequence` with a
+/// [`MultiSequenceBuilder`], and assign it with
+/// [`LookupTable::multi_sequence`](crate::flexspi::LookupTable::multi_sequence).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiSequence {
+    pub(crate) sequences: [Sequence; NUMBER_OF_SEQUENCES],
+    pub(crate) starting_lut_index: u8,
+    pub(crate) number_of_luts: u8,
+}
+
+impl MultiSequence {
+    /// Returns the LUT index of the first sequence
+    pub const fn starting_lut_index(&self) -> u8 {
+        self.starting_lut_index
+    }
+    /// Returns the number of LUT sequences
+    pub const fn number_of_luts(&self) -> u8 {
+        self.number_of_luts
+    }
+    /// Returns the sequence descriptor that matches this multi-sequence
+    ///
+    /// Use the descriptor for a [`DeviceModeConfiguration`](crate::flexspi::DeviceModeConfiguration),
+    /// or for a [`ConfigurationCommand`](crate::flexspi::ConfigurationCommand).
+    pub const fn device_mode_sequence(&self) -> DeviceModeSequence {
+        DeviceModeSequence::new(self.number_of_luts, self.starting_lut_index)
+    }
+}
+
+/// A [`MultiSequence`] builder
+///
+/// `MultiSequenceBuilder` accepts any number of instructions, and splits them across
+/// consecutive LUT sequences, starting at the LUT index you provide. Any unspecified
+/// instructions in the final sequence are set to [`STOP`]. If the instructions don't
+/// fit in the lookup table, you'll observe a compile-time error.
+///
+/// # Example
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{
+///     Command,
+///     Instr,
+///     LookupTable,
+///     MultiSequence,
+///     MultiSequenceBuilder,
+///     Pads,
+///     opcodes::sdr::*,
+/// };
+///
+/// const WRITE_REGISTERS: MultiSequence = MultiSequenceBuilder::new(6)
+///     .instr(Instr::new(CMD, Pads::One, 0x06))
+///     .instr(Instr::new(CMD, Pads::One, 0x01))
+///     .instr(Instr::new(WRITE, Pads::One, 0x01))
+///     .instr(Instr::new(CMD, Pads::One, 0x06))
+///     .instr(Instr::new(CMD, Pads::One, 0x31))
+///     .instr(Instr::new(WRITE, Pads::One, 0x01))
+///     .instr(Instr::new(CMD, Pads::One, 0x06))
+///     .instr(Instr::new(CMD, Pads::One, 0x11))
+///     .instr(Instr::new(WRITE, Pads::One, 0x01))
+///     .build();
+///
+/// assert_eq!(WRITE_REGISTERS.number_of_luts(), 2);
+///
+/// const LUT: LookupTable = LookupTable::new().multi_sequence(WRITE_REGISTERS);
+/// ```
+pub struct MultiSequenceBuilder {
+    instrs: [Instr; MAX_MULTI_SEQUENCE_INSTRUCTIONS],
+    len: usize,
+    starting_lut_index: u8,
+}
+
+impl MultiSequenceBuilder {
+    /// Creates a new `MultiSequenceBuilder` that places its first sequence at
+    /// `starting_lut_index`
+    ///
+    /// If `starting_lut_index` is not a LUT index, you'll observe a compile-time error.
+    pub const fn new(starting_lut_index: u8) -> Self {
+        if starting_lut_index as usize >= NUMBER_OF_SEQUENCES {
+            panic!("The starting LUT index must be less than 16");
+        }
+        MultiSequenceBuilder {
+            instrs: [STOP; MAX_MULTI_SEQUENCE_INSTRUCTIONS],
+            len: 0,
+            starting_lut_index,
+        }
+    }
+    /// Insert `instr` as the next instruction
+    ///
+    /// If the instructions run past the end of the lookup table, you'll observe a
+    /// compile-time error.
+    pub const fn instr(mut self, instr: Instr) -> Self {
+        let available =
+            (NUMBER_OF_SEQUENCES - self.starting_lut_index as usize) * INSTRUCTIONS_PER_SEQUENCE;
+        if self.len >= available {
+            panic!("The multi-sequence runs past the end of the lookup table");
+        }
+        self.instrs[self.len] = instr;
+        self.len += 1;
+        self
+    }
+    /// Create the multi-sequence
+    ///
+    /// If you did not insert any instructions, you'll observe a compile-time error.
+    pub const fn build(self) -> MultiSequence {
+        if self.len == 0 {
+            panic!("A multi-sequence needs at least one instruction");
+        }
+        let number_of_luts = (self.len - 1) / INSTRUCTIONS_PER_SEQUENCE + 1;
+        let mut sequences = [Sequence::stopped(); NUMBER_OF_SEQUENCES];
+        let mut idx = 0;
+        while idx < self.len {
+            sequences[idx / INSTRUCTIONS_PER_SEQUENCE].0[idx % INSTRUCTIONS_PER_SEQUENCE] =
+                self.instrs[idx];
+            idx += 1;
+        }
+        MultiSequence {
+            sequences,
+            starting_lut_index: self.starting_lut_index,
+            number_of_luts: number_of_luts as u8,
+        }
+    }
+}
+
 /// A FlexSPI opcode
 ///
 /// Available `Opcode`s are defined in the `opcodes` module.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Opcode(u8);
 
+impl Opcode {
+    /// `const` equality
+    pub(crate) const fn is(self, other: Opcode) -> bool {
+        self.0 == other.0
+    }
+    /// Returns `true` if this is one of the [`ddr`](opcodes::ddr) opcodes
+    pub(crate) const fn is_ddr(self) -> bool {
+        self.0 >= opcodes::ddr::CMD.0 && self.0 <= opcodes::ddr::DUMMY_RWDS.0
+    }
+}
+
 /// Number of pads to use to execute the instruction
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Pads {
     /// Single mode
@@ -143,6 +710,22 @@ pub enum Pads {
     Eight = 0x03,
 }
 
+impl Pads {
+    /// Decode the pads from the two least-significant bits of `raw`
+    const fn from_raw(raw: u8) -> Self {
+        match raw & 0b11 {
+            0x00 => Pads::One,
+            0x01 => Pads::Two,
+            0x02 => Pads::Four,
+            _ => Pads::Eight,
+        }
+    }
+    /// Returns the number of data lines
+    pub(crate) const fn width(self) -> u8 {
+        1 << self as u8
+    }
+}
+
 impl fmt::Display for Pads {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let pads = match *self {
@@ -253,44 +836,59 @@ pub mod opcodes {
     }
 }
 
-impl fmt::Display for Opcode {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Opcode {
+    /// Returns the opcode's name, or `None` if the opcode is unknown
+    pub(crate) const fn name(self) -> Option<&'static str> {
         use opcodes::ddr;
         use opcodes::sdr;
-        match *self {
+        let name = match self {
             // SDR
-            sdr::CMD => write!(f, "CMD_SDR"),
-            sdr::RADDR => write!(f, "RADDR_SDR"),
-            sdr::CADDR => write!(f, "CADDR_SDR"),
-            sdr::MODE1 => write!(f, "MODE1_SDR"),
-            sdr::MODE2 => write!(f, "MODE2_SDR"),
-            sdr::MODE4 => write!(f, "MODE4_SDR"),
-            sdr::MODE8 => write!(f, "MODE8_SDR"),
-            sdr::WRITE => write!(f, "WRITE_SDR"),
-            sdr::READ => write!(f, "READ_SDR"),
-            sdr::LEARN => write!(f, "LEARN_SDR"),
-            sdr::DATASZ => write!(f, "DATASZ_SDR"),
-            sdr::DUMMY => write!(f, "DUMMY_SDR"),
-            sdr::DUMMY_RWDS => write!(f, "DUMMY_RWDS_SDR"),
+            sdr::CMD => "CMD_SDR",
+            sdr::RADDR => "RADDR_SDR",
+            sdr::CADDR => "CADDR_SDR",
+            sdr::MODE1 => "MODE1_SDR",
+            sdr::MODE2 => "MODE2_SDR",
+            sdr::MODE4 => "MODE4_SDR",
+            sdr::MODE8 => "MODE8_SDR",
+            sdr::WRITE => "WRITE_SDR",
+            sdr::READ => "READ_SDR",
+            sdr::LEARN => "LEARN_SDR",
+            sdr::DATASZ => "DATASZ_SDR",
+            sdr::DUMMY => "DUMMY_SDR",
+            sdr::DUMMY_RWDS => "DUMMY_RWDS_SDR",
             // DDR
-            ddr::CMD => write!(f, "CMD_DDR"),
-            ddr::RADDR => write!(f, "RADDR_DDR"),
-            ddr::CADDR => write!(f, "CADDR_DDR"),
-            ddr::MODE1 => write!(f, "MODE1_DDR"),
-            ddr::MODE2 => write!(f, "MODE2_DDR"),
-            ddr::MODE4 => write!(f, "MODE4_DDR"),
-            ddr::MODE8 => write!(f, "MODE8_DDR"),
-            ddr::WRITE => write!(f, "WRITE_DDR"),
-            ddr::READ => write!(f, "READ_DDR"),
-            ddr::LEARN => write!(f, "LEARN_DDR"),
-            ddr::DATASZ => write!(f, "DATASZ_DDR"),
-            ddr::DUMMY => write!(f, "DUMMY_DDR"),
-            ddr::DUMMY_RWDS => write!(f, "DUMMY_RWDS_DDR"),
+            ddr::CMD => "CMD_DDR",
+            ddr::RADDR => "RADDR_DDR",
+            ddr::CADDR => "CADDR_DDR",
+            ddr::MODE1 => "MODE1_DDR",
+            ddr::MODE2 => "MODE2_DDR",
+            ddr::MODE4 => "MODE4_DDR",
+            ddr::MODE8 => "MODE8_DDR",
+            ddr::WRITE => "WRITE_DDR",
+            ddr::READ => "READ_DDR",
+            ddr::LEARN => "LEARN_DDR",
+            ddr::DATASZ => "DATASZ_DDR",
+            ddr::DUMMY => "DUMMY_DDR",
+            ddr::DUMMY_RWDS => "DUMMY_RWDS_DDR",
             // Others
-            opcodes::STOP => write!(f, "STOP"),
-            opcodes::JUMP_ON_CS => write!(f, "JUMP_ON_CS"),
-            // Should be unreachable
-            unknown => write!(f, "UNKNOWN({:#02X})", unknown.0),
+            opcodes::STOP => "STOP",
+            opcodes::JUMP_ON_CS => "JUMP_ON_CS",
+            _ => return None,
+        };
+        Some(name)
+    }
+    /// Create an opcode from the six least-significant bits of `raw`
+    pub(crate) const fn from_raw(raw: u8) -> Self {
+        Opcode(raw & 0x3F)
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            // An opcode decoded from raw bytes may not have a name.
+            None => write!(f, "UNKNOWN({:#04X})", self.0),
         }
     }
 }
@@ -306,7 +904,7 @@ mod test {
     use super::opcodes::sdr::*;
     use super::Instr;
     use super::Pads;
-    use super::{Sequence, SequenceBuilder};
+    use super::{MultiSequence, MultiSequenceBuilder, Opcode, Sequence, SequenceBuilder, STOP};
 
     fn seq_to_bytes(seq: Sequence) -> Vec<u8> {
         let mut buffer = vec![0; super::SEQUENCE_SIZE];
@@ -379,6 +977,102 @@ mod test {
         assert_eq!(&EXPECTED.to_le_bytes(), &seq_to_bytes(SEQUENCE)[..]);
     }
 
+    #[test]
+    fn multi_sequence_split() {
+        const INSTR: Instr = Instr::new(CMD, Pads::Eight, 0x5A);
+        const MULTI: MultiSequence = MultiSequenceBuilder::new(10)
+            .instr(INSTR)
+            .instr(INSTR)
+            .instr(INSTR)
+            .instr(INSTR)
+            .instr(INSTR)
+            .instr(INSTR)
+            .instr(INSTR)
+            .instr(INSTR)
+            .instr(INSTR)
+            .build();
+        assert_eq!(MULTI.number_of_luts(), 2);
+        assert_eq!(MULTI.device_mode_sequence().0, [2, 10, 0, 0]);
+        assert_eq!(MULTI.sequences[0].0, [INSTR; 8]);
+        assert_eq!(MULTI.sequences[1].0[0], INSTR);
+        assert_eq!(MULTI.sequences[1].0[1..], [STOP; 7]);
+        assert!(MULTI.sequences[2].is_stopped());
+    }
+
+    #[test]
+    fn instructions() {
+        const SEQUENCE: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x05))
+            .instr(STOP)
+            .instr(Instr::new(READ, Pads::One, 0x04))
+            .build();
+        assert_eq!(SEQUENCE.instructions()[2].opcode(), READ);
+        assert_eq!(SEQUENCE.iter().collect::<Vec<_>>(), [SEQUENCE.0[0]]);
+        assert_eq!(Instr::from_raw(0x0405), SEQUENCE.0[0]);
+    }
+
+    #[test]
+    fn display() {
+        use super::opcodes::ddr;
+        assert_eq!(
+            Instr::new(ddr::DUMMY_RWDS, Pads::Eight, 0x1D).to_string(),
+            "DUMMY_RWDS_DDR OCTAL 29 cycles"
+        );
+        assert_eq!(
+            Instr::from_raw(0x5612).to_string(),
+            "UNKNOWN(0x15) QUAD 0x12"
+        );
+        assert_eq!(Opcode::from_raw(0x15).to_string(), "UNKNOWN(0x15)");
+        assert_eq!(Opcode::from_raw(0x0E).to_string(), "UNKNOWN(0x0E)");
+        assert_eq!(Opcode::from_raw(0x01).to_string(), "CMD_SDR");
+        const SEQUENCE: Sequence = SequenceBuilder::new()
+            .instr(Instr::new(CMD, Pads::One, 0x06))
+            .instr(STOP)
+            .instr(Instr::new(CMD, Pads::One, 0x04))
+            .build();
+        assert_eq!(
+            SEQUENCE.to_string(),
+            "CMD_SDR SINGLE 0x06\nSTOP\nCMD_SDR SINGLE 0x04\nSTOP (x5)"
+        );
+        assert_eq!(Sequence::stopped().to_string(), "STOP (x8)");
+    }
+
+    #[test]
+    fn typed_constructors() {
+        use super::{opcodes::ddr, AddressBits};
+        assert_eq!(
+            Instr::cmd_ddr(Pads::Eight, 0xEE),
+            Instr::new(ddr::CMD, Pads::Eight, 0xEE)
+        );
+        assert_eq!(
+            Instr::raddr_ddr(Pads::Eight, AddressBits::B32),
+            Instr::new(ddr::RADDR, Pads::Eight, 32)
+        );
+        assert_eq!(
+            Instr::caddr(Pads::Eight, 16),
+            Instr::new(CADDR, Pads::Eight, 16)
+        );
+        assert_eq!(Instr::mode1(Pads::One, 1), Instr::new(MODE1, Pads::One, 1));
+        assert_eq!(Instr::mode2(Pads::Two, 3), Instr::new(MODE2, Pads::Two, 3));
+        assert_eq!(
+            Instr::mode4(Pads::Four, 0xF),
+            Instr::new(MODE4, Pads::Four, 0xF)
+        );
+        assert_eq!(
+            Instr::mode8(Pads::Four, 0xA5),
+            Instr::new(MODE8, Pads::Four, 0xA5)
+        );
+        assert_eq!(Instr::write(Pads::One), Instr::new(WRITE, Pads::One, 0x04));
+        assert_eq!(
+            Instr::read_ddr(Pads::Eight),
+            Instr::new(ddr::READ, Pads::Eight, 0x04)
+        );
+        assert_eq!(
+            Instr::dummy_rwds_ddr(Pads::Eight, 20),
+            Instr::new(ddr::DUMMY_RWDS, Pads::Eight, 20)
+        );
+    }
+
     #[test]
     fn teensy4_chip_erase() {
         const EXPECTED: u128 = 0x0000_0460;
@@ -430,3 +1124,87 @@ struct SequenceBuilderInstructionLimit;
 /// ```
 #[cfg(doctest)]
 struct SequenceBuilderTooManyInstructions;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+/// const LAST_SEQUENCE: MultiSequence = MultiSequenceBuilder::new(15)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .build();
+/// ```
+#[cfg(doctest)]
+struct MultiSequenceBuilderInstructionLimit;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{*, opcodes::sdr::*};
+/// const INSTR: Instr = Instr::new(CMD, Pads::One, 0x06);
+/// const LAST_SEQUENCE: MultiSequence = MultiSequenceBuilder::new(15)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR)
+///     .instr(INSTR) // <------- THIS SHOULD FAIL
+///     .build();
+/// ```
+#[cfg(doctest)]
+struct MultiSequenceBuilderTooManyInstructions;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{Instr, Pads};
+/// const MODE: Instr = Instr::mode2(Pads::Four, 0b11);
+/// ```
+#[cfg(doctest)]
+struct ModeBitsInRange;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{Instr, Pads};
+/// const MODE: Instr = Instr::mode2(Pads::Four, 0b100); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct ModeBitsOutOfRange;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::Instr;
+/// const JUMP: Instr = Instr::jump_on_cs_to(7);
+/// ```
+#[cfg(doctest)]
+struct JumpOnCsInSequence;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::Instr;
+/// const JUMP: Instr = Instr::jump_on_cs_to(8); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct JumpOnCsOutOfSequence;
@@ -0,0 +1,154 @@
+//! XIP continuous read ("performance enhance") mode
+//!
+//! In continuous read mode, the flash device accepts the next read without its
+//! command byte. The read sequence sends mode bits that keep the device in the mode,
+//! then ends with a `JUMP_ON_CS` that points past the command. Later reads start
+//! at the row address.
+
+use super::{AddressBits, Instr, Pads, Sequence, SequenceBuilder};
+
+/// The byte sent on all address pads to leave continuous read mode
+const EXIT_BYTE: u8 = 0xFF;
+
+/// A continuous read mode description
+///
+/// Use [`LookupTable::continuous_read`](super::LookupTable::continuous_read) to
+/// assign the `Read`, `RestoreNoCmd` and `ExitNoCmd` sequences.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::{AddressBits, ContinuousRead, Instr, Pads, SequenceBuilder};
+///
+/// // Quad I/O fast read (1-4-4), with continuous read mode bits 0xA0.
+/// const READ: ContinuousRead = ContinuousRead::new(0xEB, Pads::Four, AddressBits::B24, 0xA0, 4);
+///
+/// assert_eq!(
+///     READ.read_sequence(),
+///     SequenceBuilder::new()
+///         .instr(Instr::cmd(Pads::One, 0xEB))
+///         .instr(Instr::raddr(Pads::Four, AddressBits::B24))
+///         .instr(Instr::mode8(Pads::Four, 0xA0))
+///         .instr(Instr::dummy(Pads::Four, 4))
+///         .instr(Instr::read(Pads::Four))
+///         .instr(Instr::jump_on_cs_to(1))
+///         .build()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContinuousRead {
+    command: u8,
+    command_pads: Pads,
+    pads: Pads,
+    address_bits: AddressBits,
+    mode_bits: u8,
+    dummy_cycles: u8,
+}
+
+impl ContinuousRead {
+    /// Describe a continuous read
+    ///
+    /// `command` is the read command, sent on one pad. The address, mode bits, dummy
+    /// cycles and data use `pads`. `mode_bits` are the device's mode bits that
+    /// keep it in continuous read mode. `dummy_cycles` counts the cycles after the
+    /// mode bits, and may be zero.
+    pub const fn new(
+        command: u8,
+        pads: Pads,
+        address_bits: AddressBits,
+        mode_bits: u8,
+        dummy_cycles: u8,
+    ) -> Self {
+        ContinuousRead {
+            command,
+            command_pads: Pads::One,
+            pads,
+            address_bits,
+            mode_bits,
+            dummy_cycles,
+        }
+    }
+    /// Set the pads for the command byte
+    ///
+    /// The default is [`Pads::One`]. Use the same pads as the data for QPI or OPI
+    /// devices.
+    pub const fn command_pads(mut self, command_pads: Pads) -> Self {
+        self.command_pads = command_pads;
+        self
+    }
+    /// Returns the sequence that enters, and stays in, continuous read mode
+    ///
+    /// The sequence is CMD, RADDR, MODE8, DUMMY, READ and JUMP_ON_CS. The
+    /// `JUMP_ON_CS` points at the RADDR instruction.
+    pub const fn read_sequence(&self) -> Sequence {
+        let mut builder = SequenceBuilder::new()
+            .instr(Instr::cmd(self.command_pads, self.command))
+            .instr(Instr::raddr(self.pads, self.address_bits))
+            .instr(Instr::mode8(self.pads, self.mode_bits));
+        if self.dummy_cycles > 0 {
+            builder = builder.instr(Instr::dummy(self.pads, self.dummy_cycles));
+        }
+        builder
+            .instr(Instr::read(self.pads))
+            .instr(Instr::jump_on_cs_to(1))
+            .build()
+    }
+    /// Returns the sequence that re-enters continuous read mode
+    ///
+    /// This is the same as the [read sequence](ContinuousRead::read_sequence), so that
+    /// the next read skips the command.
+    pub const fn restore_sequence(&self) -> Sequence {
+        self.read_sequence()
+    }
+    /// Returns the sequence that leaves continuous read mode
+    ///
+    /// The sequence sends `0xFF` on the address pads, for as long as it takes to send
+    /// the address and the mode bits. The device sees mode bits that don't continue
+    /// the mode. The sequence ends with a `STOP`, so the next sequence starts with its
+    /// command.
+    pub const fn exit_sequence(&self) -> Sequence {
+        let bytes = self.address_bits as usize / 8 + 1;
+        let mut builder = SequenceBuilder::new();
+        let mut idx = 0;
+        while idx < bytes {
+            builder = builder.instr(Instr::cmd(self.pads, EXIT_BYTE));
+            idx += 1;
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ContinuousRead, EXIT_BYTE};
+    use crate::flexspi::{AddressBits, Command, Instr, LookupTable, Pads, SequenceBuilder};
+
+    #[test]
+    fn lookup_table() {
+        const READ: ContinuousRead =
+            ContinuousRead::new(0xEB, Pads::Four, AddressBits::B32, 0xA5, 0)
+                .command_pads(Pads::Four);
+        const LUT: LookupTable = LookupTable::new().continuous_read(READ);
+
+        let read = SequenceBuilder::new()
+            .instr(Instr::cmd(Pads::Four, 0xEB))
+            .instr(Instr::raddr(Pads::Four, AddressBits::B32))
+            .instr(Instr::mode8(Pads::Four, 0xA5))
+            .instr(Instr::read(Pads::Four))
+            .instr(Instr::jump_on_cs_to(1))
+            .build();
+        assert_eq!(LUT.get(Command::Read), &read);
+        assert_eq!(LUT.get(Command::RestoreNoCmd), &read);
+
+        let exit = Instr::cmd(Pads::Four, EXIT_BYTE);
+        assert_eq!(
+            LUT.get(Command::ExitNoCmd),
+            &SequenceBuilder::new()
+                .instr(exit)
+                .instr(exit)
+                .instr(exit)
+                .instr(exit)
+                .instr(exit)
+                .build()
+        );
+        assert!(Command::Read.check_sequence(&read).is_ok());
+    }
+}
@@ -0,0 +1,208 @@
+//! Image vector table (IVT) and boot data
+//!
+//! The boot ROM finds the image vector table at a fixed offset from the start
+//! of the boot device. The IVT tells the ROM where to find the rest of the
+//! boot-time data structures, including the [`BootData`], and the address of
+//! the image entry point. For serial NOR flash, the IVT is expected at offset
+//! `0x1000`.
+//!
+//! All addresses are absolute addresses in the processor's memory map. Since
+//! Rust cannot take the address of a `static` in a `const` context, you're
+//! responsible for providing addresses that agree with your linker script.
+//!
+//! # Example
+//!
+//! An IVT and boot data for an image that's executed in place from FlexSPI
+//! flash:
+//!
+//! ```no_run
+//! use imxrt_boot_gen::ivt::{BootData, ImageVectorTable};
+//!
+//! const FLASH_BASE: u32 = 0x6000_0000;
+//! const IVT_ADDRESS: u32 = FLASH_BASE + 0x1000;
+//! const BOOT_DATA_ADDRESS: u32 = IVT_ADDRESS + 0x20;
+//!
+//! #[no_mangle]
+//! #[link_section = ".ivt"]
+//! static IMAGE_VECTOR_TABLE: ImageVectorTable =
+//!     ImageVectorTable::new(FLASH_BASE + 0x2000)
+//!         .boot_data(BOOT_DATA_ADDRESS)
+//!         .self_address(IVT_ADDRESS);
+//!
+//! #[no_mangle]
+//! #[link_section = ".boot_data"]
+//! static BOOT_DATA: BootData = BootData::new()
+//!     .start(FLASH_BASE)
+//!     .length(8 * 1024 * 1024);
+//! ```
+
+use crate::bytes::{write_array, write_u32};
+
+/// IVT header tag
+const IVT_TAG: u8 = 0xD1;
+/// IVT header version
+///
+/// [7:4] major = 4
+/// [3:0] minor = 0
+const IVT_VERSION: u8 = 0x40;
+
+/// Size of the IVT in bytes
+const IVT_SIZE: usize = 32;
+
+/// Image vector table
+///
+/// The IVT header (tag, length and version) is computed for you. All unset
+/// pointers are `0`, which tells the ROM that the structure is not present.
+///
+/// See the [module-level documentation](crate::ivt) for an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct ImageVectorTable {
+    /// Tag, big-endian length, version
+    header: [u8; 4],
+    entry: u32,
+    _reserved0: u32,
+    dcd: u32,
+    boot_data: u32,
+    self_address: u32,
+    csf: u32,
+    _reserved1: u32,
+}
+
+impl ImageVectorTable {
+    /// Create a new image vector table with the absolute address of the
+    /// image `entry` point
+    ///
+    /// For a Cortex-M image, this is the address of the vector table.
+    pub const fn new(entry: u32) -> Self {
+        let length = (IVT_SIZE as u16).to_be_bytes();
+        ImageVectorTable {
+            header: [IVT_TAG, length[0], length[1], IVT_VERSION],
+            entry,
+            _reserved0: 0,
+            dcd: 0,
+            boot_data: 0,
+            self_address: 0,
+            csf: 0,
+            _reserved1: 0,
+        }
+    }
+    /// Set the absolute address of the device configuration data (DCD)
+    ///
+    /// If not set, this is `0`, and the ROM does not look for a DCD. See the
+    /// [`dcd`](crate::dcd) module to create a DCD.
+    pub const fn dcd(mut self, dcd: u32) -> Self {
+        self.dcd = dcd;
+        self
+    }
+    /// Set the absolute address of the [`BootData`]
+    pub const fn boot_data(mut self, boot_data: u32) -> Self {
+        self.boot_data = boot_data;
+        self
+    }
+    /// Set the absolute address of this image vector table (`self`)
+    pub const fn self_address(mut self, self_address: u32) -> Self {
+        self.self_address = self_address;
+        self
+    }
+    /// Set the absolute address of the command sequence file (CSF)
+    ///
+    /// The CSF is used by HAB for secure boot. If not set, this is `0`.
+    pub const fn csf(mut self, csf: u32) -> Self {
+        self.csf = csf;
+        self
+    }
+    /// Returns the little-endian memory representation of the image vector table
+    pub const fn to_bytes(&self) -> [u8; IVT_SIZE] {
+        let mut bytes = write_array([0; IVT_SIZE], 0, &self.header);
+        bytes = write_u32(bytes, 4, self.entry);
+        bytes = write_u32(bytes, 8, self._reserved0);
+        bytes = write_u32(bytes, 12, self.dcd);
+        bytes = write_u32(bytes, 16, self.boot_data);
+        bytes = write_u32(bytes, 20, self.self_address);
+        bytes = write_u32(bytes, 24, self.csf);
+        write_u32(bytes, 28, self._reserved1)
+    }
+}
+
+const _STATIC_ASSERT_IVT_SIZE: [u32; 1] =
+    [0; (core::mem::size_of::<ImageVectorTable>() == IVT_SIZE) as usize];
+
+/// Boot data
+///
+/// Describes the location and size of the boot image. All unset fields
+/// are `0`.
+///
+/// See the [module-level documentation](crate::ivt) for an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct BootData {
+    start: u32,
+    length: u32,
+    plugin: u32,
+    _reserved: u32,
+}
+
+impl BootData {
+    /// Create new boot data
+    pub const fn new() -> Self {
+        BootData {
+            start: 0,
+            length: 0,
+            plugin: 0,
+            _reserved: 0,
+        }
+    }
+    /// Set the absolute address of the boot image
+    ///
+    /// This is typically the start of the boot device, like the base of FlexSPI flash.
+    pub const fn start(mut self, start: u32) -> Self {
+        self.start = start;
+        self
+    }
+    /// Set the size of the boot image, in bytes
+    pub const fn length(mut self, length: u32) -> Self {
+        self.length = length;
+        self
+    }
+    /// Indicate that the image is a plugin
+    ///
+    /// If not set, the image is not a plugin.
+    pub const fn plugin(mut self, plugin: bool) -> Self {
+        self.plugin = plugin as u32;
+        self
+    }
+    /// Returns the little-endian memory representation of the boot data
+    pub const fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = write_u32([0; 16], 0, self.start);
+        bytes = write_u32(bytes, 4, self.length);
+        bytes = write_u32(bytes, 8, self.plugin);
+        write_u32(bytes, 12, self._reserved)
+    }
+}
+
+impl Default for BootData {
+    fn default() -> Self {
+        BootData::new()
+    }
+}
+
+const _STATIC_ASSERT_BOOT_DATA_SIZE: [u32; 1] =
+    [0; (core::mem::size_of::<BootData>() == 16) as usize];
+
+#[cfg(test)]
+mod test {
+    use super::{BootData, ImageVectorTable};
+
+    #[test]
+    fn ivt_header() {
+        const IVT: ImageVectorTable = ImageVectorTable::new(0);
+        assert_eq!(IVT.header, [0xD1, 0x00, 0x20, 0x40]);
+    }
+
+    #[test]
+    fn plugin() {
+        const BOOT_DATA: BootData = BootData::new().plugin(true);
+        assert_eq!(BOOT_DATA.plugin, 1);
+    }
+}
@@ -12,8 +12,11 @@
 //! As of this writing, the API supports
 //!
 //! - serial NOR flash
+//! - serial NAND flash
+//! - the image vector table (IVT) and boot data
+//! - device configuration data (DCD)
 //!
-//! Other configurations, like NAND flash and parallel SEMC, may be added in the future.
+//! Other configurations, like parallel SEMC, may be added in the future.
 //!
 //! # Usage
 //!
@@ -53,5 +56,8 @@
 
 #![cfg_attr(not(test), no_std)]
 
+mod bytes;
+pub mod dcd;
 pub mod flexspi;
+pub mod ivt;
 pub mod serial_flash;
@@ -1,7 +1,7 @@
-//! Serial NOR flash boot
+//! Serial NOR and NAND flash boot
 //!
 //! `serial_flash` provides the types necessary to boot an i.MX RT processor
-//! from serial NOR flash. *Note: NAND Flash boot not yet implemented.*
+//! from serial NOR or serial NAND flash.
 //!
 //! # Serial NOR Configuration Block
 //!
@@ -11,5 +11,27 @@
 //! Use the FlexSPI configuration block to create a Serial NOR configuration
 //! block. You are responsible for placing the serial NOR configuration block at the correct
 //! location in memory. See [`nor::ConfigurationBlock`] for an example.
+//!
+//! # Serial NAND Configuration Block
+//!
+//! Serial NAND configuration blocks are created the same way. Use the FlexSPI
+//! configuration block to create a [`nand::ConfigurationBlock`], then describe
+//! the NAND page and block geometry.
+//!
+//! # JEDEC serial NOR lookup tables
+//!
+//! Most serial NOR devices share the JEDEC command set. Instead of writing each
+//! lookup table sequence, describe the device's protocol, and generate the lookup
+//! table. See the [`jedec`] module for more details.
+//!
+//! The [`devices`] module describes common serial NOR parts. A device creates a
+//! complete serial NOR configuration block.
+//!
+//! For other devices, the [`sfdp`] module derives the lookup table and configuration
+//! block from the device's SFDP tables.
 
+pub mod devices;
+pub mod jedec;
+pub mod nand;
 pub mod nor;
+pub mod sfdp;
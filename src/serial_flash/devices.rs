@@ -0,0 +1,910 @@
+//! Serial NOR flash device descriptors
+//!
+//! A [`Device`] describes a serial NOR flash part: its JEDEC ID, its size and
+//! geometry, the I/O modes it supports, its read dummy cycles, and how to set its
+//! quad enable bit. Use a device to create a [`nor::ConfigurationBlock`].
+//!
+//! ```
+//! use imxrt_boot_gen::serial_flash::{devices, jedec::IoMode, nor};
+//!
+//! const NOR_CB: nor::ConfigurationBlock = devices::W25Q64JV.configuration_block(IoMode::QuadIo);
+//! assert!(NOR_CB.check().is_ok());
+//!
+//! assert_eq!(devices::find(devices::W25Q64JV.get_jedec_id().unwrap()), Some(&devices::W25Q64JV));
+//! ```
+//!
+//! The descriptors were collected from vendor datasheets and NXP's reference
+//! configuration blocks. They have not all been tested on hardware, and the dummy
+//! cycles only cover each device's power-on configuration. Check a descriptor
+//! against your device's datasheet before you rely on it. If your device isn't
+//! listed, describe it with [`Device::spi`].
+//!
+//! [`Device::configuration_block`] keeps the FlexSPI serial clock at its 30MHz
+//! default. To run the serial clock faster, use [`Device::configuration_block_at`].
+//! The read then uses the dummy cycles that the device needs at that frequency; see
+//! [`dummy_operand`] for the rules.
+
+use super::jedec::{DataRate, IoMode, Protocol};
+use super::nor;
+use crate::flexspi::{
+    self, opcodes::sdr, AddressBits, ColumnAddressWidth, Command, ControllerMiscOptions,
+    DataValidTime, DeviceModeConfiguration, DeviceModeSequence, Instr, LookupTable, Pads,
+    ReadSampleClockSource, Sequence, SequenceBuilder, SerialFlashRegion,
+};
+
+/// The LUT index for the quad enable sequence
+const QUAD_ENABLE_SEQUENCE: u8 = 6;
+
+/// Dummy cycles for the JEDEC 1-1-1 fast read, `0x0B`
+const FAST_READ_DUMMY_CYCLES: &[DummyCycles] =
+    &[DummyCycles::new(IoMode::Single, DataRate::Sdr, 8, 104)];
+
+/// Dummy cycles for a HyperFlash read, with the default initial latency
+const HYPERFLASH_DUMMY_CYCLES: &[DummyCycles] =
+    &[DummyCycles::new(IoMode::Opi, DataRate::Dtr, 6, 133)];
+
+/// The largest device that's addressable with 3 byte addresses
+pub(super) const THREE_BYTE_ADDRESS_LIMIT: u32 = 16 * 1024 * 1024;
+
+/// A JEDEC ID, as returned by the `0x9F` read ID command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JedecId {
+    manufacturer: u8,
+    memory_type: u8,
+    capacity: u8,
+}
+
+impl JedecId {
+    /// Create a JEDEC ID from its three bytes
+    pub const fn new(manufacturer: u8, memory_type: u8, capacity: u8) -> Self {
+        JedecId {
+            manufacturer,
+            memory_type,
+            capacity,
+        }
+    }
+    /// Returns the manufacturer ID
+    pub const fn manufacturer(self) -> u8 {
+        self.manufacturer
+    }
+    /// Returns the memory type
+    pub const fn memory_type(self) -> u8 {
+        self.memory_type
+    }
+    /// Returns the capacity code
+    pub const fn capacity(self) -> u8 {
+        self.capacity
+    }
+    const fn is(self, other: JedecId) -> bool {
+        self.manufacturer == other.manufacturer
+            && self.memory_type == other.memory_type
+            && self.capacity == other.capacity
+    }
+}
+
+/// How to set a device's quad enable (QE) bit
+///
+/// The variants follow the JESD216 quad enable requirements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuadEnable {
+    /// The device doesn't have a QE bit
+    None,
+    /// QE is bit 6 of status register 1, written with `0x01`
+    Sr1Bit6,
+    /// QE is bit 1 of status register 2, written with both status registers
+    /// after a `0x01`
+    Sr2Bit1,
+    /// QE is bit 1 of status register 2, written with `0x31`
+    Sr2Bit1WriteSr2,
+    /// QE is bit 7 of status register 2, written with `0x3E`
+    Sr2Bit7,
+}
+
+impl QuadEnable {
+    /// Returns the write status register opcode, the number of bytes, and the bytes
+    /// that set the QE bit
+    const fn write_status(self) -> Option<(u8, u8, u32)> {
+        match self {
+            QuadEnable::None => None,
+            QuadEnable::Sr1Bit6 => Some((0x01, 1, 1 << 6)),
+            QuadEnable::Sr2Bit1 => Some((0x01, 2, (1 << 1) << 8)),
+            QuadEnable::Sr2Bit1WriteSr2 => Some((0x31, 1, 1 << 1)),
+            QuadEnable::Sr2Bit7 => Some((0x3E, 1, 1 << 7)),
+        }
+    }
+}
+
+/// The read dummy cycles for an I/O mode and data rate
+///
+/// The dummy cycles are serial clock cycles, and include any mode bit cycles. They
+/// are valid up to the maximum frequency. A device that needs more dummy cycles
+/// at higher frequencies has one entry per frequency range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DummyCycles {
+    io_mode: IoMode,
+    data_rate: DataRate,
+    cycles: u8,
+    max_frequency_mhz: u16,
+}
+
+impl DummyCycles {
+    /// Describe the dummy cycles for reads up to `max_frequency_mhz`
+    pub const fn new(
+        io_mode: IoMode,
+        data_rate: DataRate,
+        cycles: u8,
+        max_frequency_mhz: u16,
+    ) -> Self {
+        DummyCycles {
+            io_mode,
+            data_rate,
+            cycles,
+            max_frequency_mhz,
+        }
+    }
+    /// Returns the I/O mode
+    pub const fn io_mode(self) -> IoMode {
+        self.io_mode
+    }
+    /// Returns the data rate
+    pub const fn data_rate(self) -> DataRate {
+        self.data_rate
+    }
+    /// Returns the number of dummy cycles
+    pub const fn cycles(self) -> u8 {
+        self.cycles
+    }
+    /// Returns the maximum frequency, in MHz
+    pub const fn max_frequency_mhz(self) -> u16 {
+        self.max_frequency_mhz
+    }
+}
+
+/// Returns the `DUMMY` operand for reads at the serial clock frequency
+///
+/// The operand comes from the `dummy_cycles` entry for `io_mode` and `data_rate`
+/// with the lowest maximum frequency that covers `frequency`.
+///
+/// # SDR and DDR
+///
+/// Datasheets give dummy cycles in serial clock cycles for both SDR and DDR reads.
+/// FlexSPI counts a `DUMMY_SDR` operand in serial clock cycles. It also counts a
+/// `DUMMY_DDR` operand in serial clock cycles, not in DDR half cycles, so the
+/// operand isn't halved or doubled for DDR reads. NXP's EVK configuration blocks
+/// use this rule: the i.MX RT1050 EVKB HyperFlash read has a `DUMMY_DDR` operand
+/// of 6 for 6 cycles of initial latency, and the i.MX RT500 EVK MX25UM51345G read
+/// has a `DUMMY_DDR` operand of `0x14` for 20 cycles. `tests/jedec.rs` compares
+/// both reads with these configuration blocks.
+///
+/// If no entry covers `frequency`, you'll observe a compile-time error.
+///
+/// ```
+/// use imxrt_boot_gen::flexspi::SerialClockFrequency;
+/// use imxrt_boot_gen::serial_flash::devices::{dummy_operand, DummyCycles};
+/// use imxrt_boot_gen::serial_flash::jedec::{DataRate, IoMode};
+///
+/// const DUMMY_CYCLES: &[DummyCycles] = &[
+///     DummyCycles::new(IoMode::QuadIo, DataRate::Sdr, 6, 104),
+///     DummyCycles::new(IoMode::QuadIo, DataRate::Sdr, 8, 133),
+///     DummyCycles::new(IoMode::QuadIo, DataRate::Dtr, 8, 100),
+/// ];
+///
+/// const SDR_60MHZ: u8 = dummy_operand(DUMMY_CYCLES, IoMode::QuadIo, DataRate::Sdr, SerialClockFrequency::MHz60);
+/// const SDR_133MHZ: u8 = dummy_operand(DUMMY_CYCLES, IoMode::QuadIo, DataRate::Sdr, SerialClockFrequency::MHz133);
+/// const DTR_100MHZ: u8 = dummy_operand(DUMMY_CYCLES, IoMode::QuadIo, DataRate::Dtr, SerialClockFrequency::MHz100);
+///
+/// assert_eq!(SDR_60MHZ, 6);
+/// assert_eq!(SDR_133MHZ, 8);
+/// assert_eq!(DTR_100MHZ, 8);
+/// ```
+pub const fn dummy_operand(
+    dummy_cycles: &[DummyCycles],
+    io_mode: IoMode,
+    data_rate: DataRate,
+    frequency: flexspi::SerialClockFrequency,
+) -> u8 {
+    let mhz = frequency.mhz();
+    let mut selected: Option<DummyCycles> = None;
+    let mut idx = 0;
+    while idx < dummy_cycles.len() {
+        let entry = dummy_cycles[idx];
+        if entry.io_mode as u8 == io_mode as u8
+            && entry.data_rate as u8 == data_rate as u8
+            && entry.max_frequency_mhz >= mhz
+        {
+            selected = match selected {
+                Some(current) if current.max_frequency_mhz <= entry.max_frequency_mhz => {
+                    Some(current)
+                }
+                _ => Some(entry),
+            };
+        }
+        idx += 1;
+    }
+    match selected {
+        Some(entry) => match data_rate {
+            // Both count serial clock cycles; see "SDR and DDR."
+            DataRate::Sdr | DataRate::Dtr => entry.cycles,
+        },
+        None => panic!("The device doesn't support the serial clock frequency in the I/O mode"),
+    }
+}
+
+/// The bus that connects the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interface {
+    /// SPI, QPI or OPI
+    Spi,
+    /// HyperBus
+    HyperFlash,
+}
+
+/// A serial NOR flash device descriptor
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Device {
+    name: &'static str,
+    interface: Interface,
+    jedec_id: Option<JedecId>,
+    size: u32,
+    page_size: u32,
+    sector_size: u32,
+    block_size: u32,
+    io_modes: &'static [IoMode],
+    dummy_cycles: &'static [DummyCycles],
+    quad_enable: QuadEnable,
+}
+
+impl Device {
+    /// Describe a SPI device of `size` bytes
+    ///
+    /// The device has 256 byte pages, 4KiB sectors and 64KiB blocks. It only
+    /// supports the 1-1-1 I/O mode, reading with the JEDEC `0x0B` fast read and
+    /// its 8 dummy cycles up to 104MHz. It has no QE bit.
+    pub const fn spi(name: &'static str, jedec_id: JedecId, size: u32) -> Self {
+        Device {
+            name,
+            interface: Interface::Spi,
+            jedec_id: Some(jedec_id),
+            size,
+            page_size: 256,
+            sector_size: 4 * 1024,
+            block_size: 64 * 1024,
+            io_modes: &[IoMode::Single],
+            dummy_cycles: FAST_READ_DUMMY_CYCLES,
+            quad_enable: QuadEnable::None,
+        }
+    }
+    /// Describe a HyperFlash device of `size` bytes
+    ///
+    /// HyperFlash devices identify themselves through their CFI ID space, so they
+    /// don't have a JEDEC ID. The device has 512 byte pages, and 256KiB sectors and
+    /// blocks. It supports the 8 pad DDR I/O mode, [`IoMode::Opi`], with the default
+    /// initial latency of 6 cycles up to 133MHz.
+    pub const fn hyperflash(name: &'static str, size: u32) -> Self {
+        Device {
+            name,
+            interface: Interface::HyperFlash,
+            jedec_id: None,
+            size,
+            page_size: 512,
+            sector_size: 256 * 1024,
+            block_size: 256 * 1024,
+            io_modes: &[IoMode::Opi],
+            dummy_cycles: HYPERFLASH_DUMMY_CYCLES,
+            quad_enable: QuadEnable::None,
+        }
+    }
+    /// Set the page, sector and block sizes, in bytes
+    pub const fn geometry(mut self, page_size: u32, sector_size: u32, block_size: u32) -> Self {
+        self.page_size = page_size;
+        self.sector_size = sector_size;
+        self.block_size = block_size;
+        self
+    }
+    /// Set the supported I/O modes
+    ///
+    /// Only list the modes that [`configuration_block`](Device::configuration_block)
+    /// supports. For SPI devices, those are the 1-x-x modes.
+    pub const fn io_modes(mut self, io_modes: &'static [IoMode]) -> Self {
+        self.io_modes = io_modes;
+        self
+    }
+    /// Set the read dummy cycles
+    pub const fn dummy_cycles(mut self, dummy_cycles: &'static [DummyCycles]) -> Self {
+        self.dummy_cycles = dummy_cycles;
+        self
+    }
+    /// Set the quad enable method
+    pub const fn quad_enable(mut self, quad_enable: QuadEnable) -> Self {
+        self.quad_enable = quad_enable;
+        self
+    }
+
+    /// Returns the part name
+    pub const fn get_name(&self) -> &'static str {
+        self.name
+    }
+    /// Returns the bus that connects the device
+    pub const fn get_interface(&self) -> Interface {
+        self.interface
+    }
+    /// Returns the JEDEC ID, or `None` for HyperFlash devices
+    pub const fn get_jedec_id(&self) -> Option<JedecId> {
+        self.jedec_id
+    }
+    /// Returns the size, in bytes
+    pub const fn get_size(&self) -> u32 {
+        self.size
+    }
+    /// Returns the page size, in bytes
+    pub const fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+    /// Returns the sector size, in bytes
+    pub const fn get_sector_size(&self) -> u32 {
+        self.sector_size
+    }
+    /// Returns the block size, in bytes
+    pub const fn get_block_size(&self) -> u32 {
+        self.block_size
+    }
+    /// Returns the supported I/O modes
+    pub const fn get_io_modes(&self) -> &'static [IoMode] {
+        self.io_modes
+    }
+    /// Returns the read dummy cycles
+    pub const fn get_dummy_cycles(&self) -> &'static [DummyCycles] {
+        self.dummy_cycles
+    }
+    /// Returns the quad enable method
+    pub const fn get_quad_enable(&self) -> QuadEnable {
+        self.quad_enable
+    }
+    /// Returns `true` if the device supports the I/O mode
+    pub const fn supports(&self, io_mode: IoMode) -> bool {
+        let mut idx = 0;
+        while idx < self.io_modes.len() {
+            if self.io_modes[idx] as u8 == io_mode as u8 {
+                return true;
+            }
+            idx += 1;
+        }
+        false
+    }
+
+    /// Create a serial NOR configuration block that reads with `io_mode`
+    ///
+    /// If the I/O mode needs the QE bit, the configuration block sets it with the
+    /// device mode configuration. SPI devices are limited to the 1-x-x I/O modes,
+    /// since the configuration block can't switch a device into QPI or OPI mode.
+    ///
+    /// If the device doesn't support `io_mode`, or if there are no dummy cycles for
+    /// `io_mode`, you'll observe a compile-time error.
+    pub const fn configuration_block(&self, io_mode: IoMode) -> nor::ConfigurationBlock {
+        self.configuration_block_at(io_mode, flexspi::SerialClockFrequency::MHz30)
+    }
+    /// Create a serial NOR configuration block that reads with `io_mode` at the serial
+    /// clock frequency
+    ///
+    /// This is the same as [`configuration_block`](Device::configuration_block), but
+    /// the FlexSPI serial clock runs at `frequency`. The read's `DUMMY` operand comes
+    /// from [`dummy_operand`].
+    ///
+    /// If the device can't read with `io_mode` at `frequency`, you'll observe a
+    /// compile-time error.
+    pub const fn configuration_block_at(
+        &self,
+        io_mode: IoMode,
+        frequency: flexspi::SerialClockFrequency,
+    ) -> nor::ConfigurationBlock {
+        if !self.supports(io_mode) {
+            panic!("The device doesn't support the I/O mode");
+        }
+        let mem_cfg = match self.interface {
+            Interface::Spi => self.spi_configuration_block(io_mode, frequency),
+            Interface::HyperFlash => self.hyperflash_configuration_block(frequency),
+        };
+        nor::ConfigurationBlock::new(mem_cfg.serial_clk_freq(frequency))
+            .page_size(self.page_size)
+            .sector_size(self.sector_size)
+            .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30)
+    }
+
+    const fn spi_configuration_block(
+        &self,
+        io_mode: IoMode,
+        frequency: flexspi::SerialClockFrequency,
+    ) -> flexspi::ConfigurationBlock {
+        let address_bits = if self.size > THREE_BYTE_ADDRESS_LIMIT {
+            AddressBits::B32
+        } else {
+            AddressBits::B24
+        };
+        let protocol = Protocol::new(io_mode, address_bits).read_dummy_cycles(dummy_operand(
+            self.dummy_cycles,
+            io_mode,
+            DataRate::Sdr,
+            frequency,
+        ));
+        spi_configuration_block(&protocol, self.quad_enable, self.size)
+    }
+
+    const fn hyperflash_configuration_block(
+        &self,
+        frequency: flexspi::SerialClockFrequency,
+    ) -> flexspi::ConfigurationBlock {
+        let read: Sequence = SequenceBuilder::new()
+            .instr(Instr::cmd_ddr(Pads::Eight, 0xA0))
+            .instr(Instr::raddr_ddr(Pads::Eight, AddressBits::B24))
+            .instr(Instr::caddr_ddr(Pads::Eight, 16))
+            .instr(Instr::dummy_ddr(
+                Pads::Eight,
+                dummy_operand(self.dummy_cycles, IoMode::Opi, DataRate::Dtr, frequency),
+            ))
+            .instr(Instr::read_ddr(Pads::Eight))
+            .build();
+        flexspi::ConfigurationBlock::new(LookupTable::new().command(Command::Read, read))
+            .read_sample_clk_src(ReadSampleClockSource::FlashProvidedDQS)
+            .cs_hold_time(3)
+            .cs_setup_time(3)
+            .column_address_width(ColumnAddressWidth::Hyperflash)
+            .controller_misc_options(
+                ControllerMiscOptions::DDR_MODE
+                    .union(ControllerMiscOptions::WORD_ADDRESSABLE)
+                    .union(ControllerMiscOptions::SAFE_CONFIG_FREQ)
+                    .union(ControllerMiscOptions::DIFFERENTIAL_CLOCK),
+            )
+            .serial_flash_pad_type(flexspi::FlashPadType::Octal)
+            .data_valid_time(DataValidTime::from_tenths_ns(16, 16))
+            .flash_size(SerialFlashRegion::A1, self.size)
+    }
+}
+
+/// Create a FlexSPI configuration block for a SPI device that uses `protocol`
+///
+/// If the protocol's I/O mode needs the QE bit, the configuration block sets it
+/// with the device mode configuration.
+pub(super) const fn spi_configuration_block(
+    protocol: &Protocol,
+    quad_enable: QuadEnable,
+    size: u32,
+) -> flexspi::ConfigurationBlock {
+    let io_mode = protocol.io_mode();
+    if matches!(io_mode, IoMode::Qpi | IoMode::Opi) {
+        panic!("Switching a device into QPI or OPI mode isn't supported");
+    }
+    let mut lookup_table = protocol.lookup_table();
+    let mut device_mode_configuration = DeviceModeConfiguration::Disabled;
+    if matches!(io_mode, IoMode::QuadOutput | IoMode::QuadIo) {
+        if let Some((opcode, len, arg)) = quad_enable.write_status() {
+            lookup_table = lookup_table.command(
+                Command::Custom(QUAD_ENABLE_SEQUENCE),
+                SequenceBuilder::new()
+                    .instr(Instr::cmd(Pads::One, opcode))
+                    .instr(Instr::new(sdr::WRITE, Pads::One, len))
+                    .build(),
+            );
+            device_mode_configuration = DeviceModeConfiguration::Enabled {
+                device_mode_arg: arg,
+                device_mode_seq: DeviceModeSequence::new(1, QUAD_ENABLE_SEQUENCE),
+            };
+        }
+    }
+    flexspi::ConfigurationBlock::new(lookup_table)
+        .read_sample_clk_src(ReadSampleClockSource::LoopbackFromDQSPad)
+        .device_mode_configuration(device_mode_configuration)
+        .serial_flash_pad_type(io_mode.flash_pad_type())
+        .flash_size(SerialFlashRegion::A1, size)
+}
+
+/// Returns the device with the JEDEC ID, or `None` if there's no such device
+pub const fn find(jedec_id: JedecId) -> Option<&'static Device> {
+    let mut idx = 0;
+    while idx < ALL.len() {
+        let device = ALL[idx];
+        if let Some(id) = device.jedec_id {
+            if id.is(jedec_id) {
+                return Some(device);
+            }
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// All device descriptors
+pub const ALL: &[&Device] = &[
+    &W25Q16JV,
+    &W25Q32JV,
+    &W25Q64JV,
+    &W25Q128JV,
+    &W25Q256JV,
+    &IS25LP064A,
+    &IS25LP128F,
+    &IS25WP064A,
+    &IS25WP128F,
+    &MX25L6433F,
+    &MX25L12835F,
+    &MX25UM51345G,
+    &AT25SF321,
+    &AT25SF128A,
+    &ATXP032,
+    &ATXP064,
+    &GD25Q64C,
+    &GD25Q128C,
+    &MT25QL128ABA,
+    &MT25QL256ABA,
+    &MT25QU128ABA,
+    &MT35XU512ABA,
+    &S25FL064L,
+    &S25FL128L,
+    &S25FL256L,
+    &S26KS512S,
+];
+
+const MIB: u32 = 1024 * 1024;
+
+const SPI_MODES: &[IoMode] = &[
+    IoMode::Single,
+    IoMode::DualOutput,
+    IoMode::DualIo,
+    IoMode::QuadOutput,
+    IoMode::QuadIo,
+];
+
+/// Octal devices boot in 1-1-1 mode, and the configuration block can't switch them
+/// into OPI mode.
+const OCTAL_MODES: &[IoMode] = &[IoMode::Single];
+
+/// Dummy cycles for the 1-1-1 read of an octal device
+const OCTAL_DUMMY_CYCLES: &[DummyCycles] =
+    &[DummyCycles::new(IoMode::Single, DataRate::Sdr, 8, 133)];
+
+/// Dummy cycles for the JEDEC 1-x-x reads, up to `max_frequency_mhz`
+const fn spi_dummy_cycles(max_frequency_mhz: u16) -> [DummyCycles; 5] {
+    [
+        DummyCycles::new(IoMode::Single, DataRate::Sdr, 8, max_frequency_mhz),
+        DummyCycles::new(IoMode::DualOutput, DataRate::Sdr, 8, max_frequency_mhz),
+        DummyCycles::new(IoMode::DualIo, DataRate::Sdr, 4, max_frequency_mhz),
+        DummyCycles::new(IoMode::QuadOutput, DataRate::Sdr, 8, max_frequency_mhz),
+        DummyCycles::new(IoMode::QuadIo, DataRate::Sdr, 6, max_frequency_mhz),
+    ]
+}
+
+const SPI_DUMMY_CYCLES_133MHZ: &[DummyCycles] = &spi_dummy_cycles(133);
+const SPI_DUMMY_CYCLES_104MHZ: &[DummyCycles] = &spi_dummy_cycles(104);
+
+//
+// Winbond
+//
+
+const fn winbond(name: &'static str, capacity: u8, size: u32) -> Device {
+    Device::spi(name, JedecId::new(0xEF, 0x40, capacity), size)
+        .io_modes(SPI_MODES)
+        .dummy_cycles(SPI_DUMMY_CYCLES_133MHZ)
+        .quad_enable(QuadEnable::Sr2Bit1WriteSr2)
+}
+
+/// Winbond W25Q16JV, 2MiB
+pub const W25Q16JV: Device = winbond("W25Q16JV", 0x15, 2 * MIB);
+/// Winbond W25Q32JV, 4MiB
+pub const W25Q32JV: Device = winbond("W25Q32JV", 0x16, 4 * MIB);
+/// Winbond W25Q64JV, 8MiB
+pub const W25Q64JV: Device = winbond("W25Q64JV", 0x17, 8 * MIB);
+/// Winbond W25Q128JV, 16MiB
+pub const W25Q128JV: Device = winbond("W25Q128JV", 0x18, 16 * MIB);
+/// Winbond W25Q256JV, 32MiB
+pub const W25Q256JV: Device = winbond("W25Q256JV", 0x19, 32 * MIB);
+
+//
+// ISSI
+//
+
+const fn issi(name: &'static str, memory_type: u8, capacity: u8, size: u32) -> Device {
+    Device::spi(name, JedecId::new(0x9D, memory_type, capacity), size)
+        .io_modes(SPI_MODES)
+        .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+        .quad_enable(QuadEnable::Sr1Bit6)
+}
+
+/// ISSI IS25LP064A, 8MiB, 3V
+pub const IS25LP064A: Device = issi("IS25LP064A", 0x60, 0x17, 8 * MIB);
+/// ISSI IS25LP128F, 16MiB, 3V
+pub const IS25LP128F: Device = issi("IS25LP128F", 0x60, 0x18, 16 * MIB);
+/// ISSI IS25WP064A, 8MiB, 1.8V
+pub const IS25WP064A: Device = issi("IS25WP064A", 0x70, 0x17, 8 * MIB);
+/// ISSI IS25WP128F, 16MiB, 1.8V
+pub const IS25WP128F: Device = issi("IS25WP128F", 0x70, 0x18, 16 * MIB);
+
+//
+// Macronix
+//
+
+/// Macronix MX25L6433F, 8MiB
+pub const MX25L6433F: Device = Device::spi("MX25L6433F", JedecId::new(0xC2, 0x20, 0x17), 8 * MIB)
+    .io_modes(SPI_MODES)
+    .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+    .quad_enable(QuadEnable::Sr1Bit6);
+/// Macronix MX25L12835F, 16MiB
+pub const MX25L12835F: Device =
+    Device::spi("MX25L12835F", JedecId::new(0xC2, 0x20, 0x18), 16 * MIB)
+        .io_modes(SPI_MODES)
+        .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+        .quad_enable(QuadEnable::Sr1Bit6);
+/// Macronix MX25UM51345G, 64MiB, octal
+pub const MX25UM51345G: Device =
+    Device::spi("MX25UM51345G", JedecId::new(0xC2, 0x81, 0x3A), 64 * MIB)
+        .io_modes(OCTAL_MODES)
+        .dummy_cycles(OCTAL_DUMMY_CYCLES);
+
+//
+// Adesto / Renesas
+//
+
+/// Adesto AT25SF321, 4MiB
+pub const AT25SF321: Device = Device::spi("AT25SF321", JedecId::new(0x1F, 0x87, 0x01), 4 * MIB)
+    .io_modes(SPI_MODES)
+    .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+    .quad_enable(QuadEnable::Sr2Bit1);
+/// Adesto AT25SF128A, 16MiB
+pub const AT25SF128A: Device = Device::spi("AT25SF128A", JedecId::new(0x1F, 0x89, 0x01), 16 * MIB)
+    .io_modes(SPI_MODES)
+    .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+    .quad_enable(QuadEnable::Sr2Bit1);
+/// Adesto ATXP032, 4MiB, octal
+pub const ATXP032: Device = Device::spi("ATXP032", JedecId::new(0x43, 0xA7, 0x00), 4 * MIB)
+    .io_modes(OCTAL_MODES)
+    .dummy_cycles(OCTAL_DUMMY_CYCLES);
+/// Adesto ATXP064, 8MiB, octal
+pub const ATXP064: Device = Device::spi("ATXP064", JedecId::new(0x43, 0xA8, 0x00), 8 * MIB)
+    .io_modes(OCTAL_MODES)
+    .dummy_cycles(OCTAL_DUMMY_CYCLES);
+
+//
+// GigaDevice
+//
+
+/// GigaDevice GD25Q64C, 8MiB
+pub const GD25Q64C: Device = Device::spi("GD25Q64C", JedecId::new(0xC8, 0x40, 0x17), 8 * MIB)
+    .io_modes(SPI_MODES)
+    .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+    .quad_enable(QuadEnable::Sr2Bit1);
+/// GigaDevice GD25Q128C, 16MiB
+pub const GD25Q128C: Device = Device::spi("GD25Q128C", JedecId::new(0xC8, 0x40, 0x18), 16 * MIB)
+    .io_modes(SPI_MODES)
+    .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+    .quad_enable(QuadEnable::Sr2Bit1);
+
+//
+// Micron
+//
+
+/// Micron MT25Q dummy cycles, in the default configuration
+const MT25Q_DUMMY_CYCLES: &[DummyCycles] = &[
+    DummyCycles::new(IoMode::Single, DataRate::Sdr, 8, 133),
+    DummyCycles::new(IoMode::DualOutput, DataRate::Sdr, 8, 133),
+    DummyCycles::new(IoMode::DualIo, DataRate::Sdr, 8, 133),
+    DummyCycles::new(IoMode::QuadOutput, DataRate::Sdr, 8, 133),
+    DummyCycles::new(IoMode::QuadIo, DataRate::Sdr, 10, 133),
+];
+
+/// Micron MT25QL128ABA, 16MiB, 3V
+pub const MT25QL128ABA: Device =
+    Device::spi("MT25QL128ABA", JedecId::new(0x20, 0xBA, 0x18), 16 * MIB)
+        .io_modes(SPI_MODES)
+        .dummy_cycles(MT25Q_DUMMY_CYCLES);
+/// Micron MT25QL256ABA, 32MiB, 3V
+pub const MT25QL256ABA: Device =
+    Device::spi("MT25QL256ABA", JedecId::new(0x20, 0xBA, 0x19), 32 * MIB)
+        .io_modes(SPI_MODES)
+        .dummy_cycles(MT25Q_DUMMY_CYCLES);
+/// Micron MT25QU128ABA, 16MiB, 1.8V
+pub const MT25QU128ABA: Device =
+    Device::spi("MT25QU128ABA", JedecId::new(0x20, 0xBB, 0x18), 16 * MIB)
+        .io_modes(SPI_MODES)
+        .dummy_cycles(MT25Q_DUMMY_CYCLES);
+/// Micron MT35XU512ABA, 64MiB, octal
+pub const MT35XU512ABA: Device =
+    Device::spi("MT35XU512ABA", JedecId::new(0x2C, 0x5B, 0x1A), 64 * MIB)
+        .io_modes(OCTAL_MODES)
+        .dummy_cycles(OCTAL_DUMMY_CYCLES);
+
+//
+// Cypress / Infineon
+//
+
+/// Cypress S25FL064L, 8MiB
+pub const S25FL064L: Device = Device::spi("S25FL064L", JedecId::new(0x01, 0x60, 0x17), 8 * MIB)
+    .io_modes(SPI_MODES)
+    .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+    .quad_enable(QuadEnable::Sr2Bit1);
+/// Cypress S25FL128L, 16MiB
+pub const S25FL128L: Device = Device::spi("S25FL128L", JedecId::new(0x01, 0x60, 0x18), 16 * MIB)
+    .io_modes(SPI_MODES)
+    .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+    .quad_enable(QuadEnable::Sr2Bit1);
+/// Cypress S25FL256L, 32MiB
+pub const S25FL256L: Device = Device::spi("S25FL256L", JedecId::new(0x01, 0x60, 0x19), 32 * MIB)
+    .io_modes(SPI_MODES)
+    .dummy_cycles(SPI_DUMMY_CYCLES_104MHZ)
+    .quad_enable(QuadEnable::Sr2Bit1);
+/// Cypress S26KS512S HyperFlash, 64MiB
+pub const S26KS512S: Device = Device::hyperflash("S26KS512S", 64 * MIB);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quad_enable() {
+        const NOR_CB: nor::ConfigurationBlock = W25Q64JV.configuration_block(IoMode::QuadIo);
+        assert!(NOR_CB.check().is_ok());
+        let mem_cfg = NOR_CB.get_mem_cfg();
+        assert_eq!(
+            mem_cfg.get_device_mode_configuration(),
+            DeviceModeConfiguration::Enabled {
+                device_mode_arg: 0x02,
+                device_mode_seq: DeviceModeSequence::new(1, QUAD_ENABLE_SEQUENCE),
+            }
+        );
+        assert_eq!(
+            mem_cfg
+                .get_lookup_table()
+                .get(Command::Custom(QUAD_ENABLE_SEQUENCE))
+                .to_string(),
+            "CMD_SDR SINGLE 0x31\nWRITE_SDR SINGLE 0x01\nSTOP (x6)"
+        );
+        assert_eq!(
+            mem_cfg.get_serial_flash_pad_type(),
+            flexspi::FlashPadType::Quad
+        );
+        assert_eq!(mem_cfg.get_flash_size(SerialFlashRegion::A1), 8 * MIB);
+
+        // No QE bit for dual reads
+        const DUAL: nor::ConfigurationBlock = W25Q64JV.configuration_block(IoMode::DualIo);
+        assert_eq!(
+            DUAL.get_mem_cfg().get_device_mode_configuration(),
+            DeviceModeConfiguration::Disabled
+        );
+    }
+
+    #[test]
+    fn four_byte_addresses() {
+        const NOR_CB: nor::ConfigurationBlock = MT25QL256ABA.configuration_block(IoMode::QuadIo);
+        assert!(NOR_CB.check().is_ok());
+        let read = *NOR_CB.get_mem_cfg().get_lookup_table().get(Command::Read);
+        assert_eq!(
+            read.to_string(),
+            "CMD_SDR SINGLE 0xEC\nRADDR_SDR QUAD 32 bits\nDUMMY_SDR QUAD 10 cycles\nREAD_SDR QUAD 0x04\nSTOP (x4)"
+        );
+    }
+
+    #[test]
+    fn hyperflash() {
+        const NOR_CB: nor::ConfigurationBlock = S26KS512S.configuration_block(IoMode::Opi);
+        assert!(NOR_CB.check().is_ok());
+        assert_eq!(NOR_CB.get_page_size(), 512);
+        assert_eq!(NOR_CB.get_sector_size(), 256 * 1024);
+        let read = *NOR_CB.get_mem_cfg().get_lookup_table().get(Command::Read);
+        assert_eq!(
+            read.instructions()[3].to_string(),
+            "DUMMY_DDR OCTAL 6 cycles"
+        );
+    }
+
+    #[test]
+    fn serial_clock_frequency() {
+        const DUMMY_CYCLES: &[DummyCycles] = &[
+            DummyCycles::new(IoMode::QuadIo, DataRate::Sdr, 10, 133),
+            DummyCycles::new(IoMode::QuadIo, DataRate::Sdr, 4, 80),
+            DummyCycles::new(IoMode::QuadIo, DataRate::Sdr, 6, 104),
+            DummyCycles::new(IoMode::QuadIo, DataRate::Dtr, 6, 66),
+        ];
+        use flexspi::SerialClockFrequency::*;
+        let sdr = |frequency| dummy_operand(DUMMY_CYCLES, IoMode::QuadIo, DataRate::Sdr, frequency);
+        assert_eq!(sdr(MHz30), 4);
+        assert_eq!(sdr(MHz80), 4);
+        assert_eq!(sdr(MHz100), 6);
+        assert_eq!(sdr(MHz120), 10);
+        assert_eq!(sdr(MHz133), 10);
+        assert_eq!(
+            dummy_operand(DUMMY_CYCLES, IoMode::QuadIo, DataRate::Dtr, MHz60),
+            6
+        );
+
+        const NOR_CB: nor::ConfigurationBlock =
+            W25Q64JV.configuration_block_at(IoMode::QuadIo, flexspi::SerialClockFrequency::MHz133);
+        assert!(NOR_CB.check().is_ok());
+        assert_eq!(NOR_CB.get_mem_cfg().get_serial_clk_freq(), MHz133);
+        assert_eq!(
+            NOR_CB.get_mem_cfg(),
+            W25Q64JV
+                .configuration_block(IoMode::QuadIo)
+                .get_mem_cfg()
+                .serial_clk_freq(MHz133)
+        );
+    }
+
+    #[test]
+    fn default_dummy_cycles() {
+        const SPI: Device = Device::spi("SPI", JedecId::new(0xEF, 0x40, 0x17), 8 * MIB);
+        const NOR_CB: nor::ConfigurationBlock = SPI.configuration_block(IoMode::Single);
+        assert!(NOR_CB.check().is_ok());
+        let read = *NOR_CB.get_mem_cfg().get_lookup_table().get(Command::Read);
+        assert_eq!(
+            read.to_string(),
+            "CMD_SDR SINGLE 0x0B\nRADDR_SDR SINGLE 24 bits\nDUMMY_SDR SINGLE 8 cycles\nREAD_SDR SINGLE 0x04\nSTOP (x4)"
+        );
+
+        const HYPERFLASH: Device = Device::hyperflash("HyperFlash", 64 * MIB);
+        const HYPERFLASH_CB: nor::ConfigurationBlock = HYPERFLASH.configuration_block(IoMode::Opi);
+        assert!(HYPERFLASH_CB.check().is_ok());
+    }
+
+    #[test]
+    fn all_devices() {
+        for device in ALL {
+            assert!(!device.get_io_modes().is_empty());
+            for io_mode in device.get_io_modes() {
+                let nor_cb = device.configuration_block(*io_mode);
+                assert_eq!(
+                    nor_cb.check(),
+                    Ok(()),
+                    "{} {:?}",
+                    device.get_name(),
+                    io_mode
+                );
+            }
+            if let Some(id) = device.get_jedec_id() {
+                assert_eq!(find(id), Some(*device));
+            }
+        }
+        assert_eq!(find(JedecId::new(0, 0, 0)), None);
+    }
+}
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::serial_flash::{devices, jedec::IoMode, nor};
+/// const NOR_CB: nor::ConfigurationBlock = devices::W25Q64JV.configuration_block(IoMode::QuadIo);
+/// ```
+#[cfg(doctest)]
+struct DeviceSupportsIoMode;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::serial_flash::{devices, jedec::IoMode, nor};
+/// const NOR_CB: nor::ConfigurationBlock = devices::W25Q64JV.configuration_block(IoMode::Qpi); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct DeviceDoesNotSupportIoMode;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::SerialClockFrequency;
+/// use imxrt_boot_gen::serial_flash::{devices, jedec::IoMode, nor};
+/// const NOR_CB: nor::ConfigurationBlock = devices::MX25L6433F.configuration_block_at(IoMode::QuadIo, SerialClockFrequency::MHz100);
+/// ```
+#[cfg(doctest)]
+struct DummyCyclesCoverFrequency;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::SerialClockFrequency;
+/// use imxrt_boot_gen::serial_flash::{devices, jedec::IoMode, nor};
+/// const NOR_CB: nor::ConfigurationBlock = devices::MX25L6433F.configuration_block_at(IoMode::QuadIo, SerialClockFrequency::MHz120); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct DummyCyclesDoNotCoverFrequency;
@@ -0,0 +1,535 @@
+//! JEDEC serial NOR lookup tables
+//!
+//! Most serial NOR flash devices implement the same JEDEC command set. A [`Protocol`]
+//! describes how your device uses that command set: the I/O mode, the data rate, the
+//! address width, the read dummy cycles, and the opcodes. Use the protocol to generate
+//! a complete [`LookupTable`], instead of writing each sequence by hand.
+//!
+//! ```
+//! use imxrt_boot_gen::flexspi::{AddressBits, Command, LookupTable};
+//! use imxrt_boot_gen::serial_flash::jedec::{IoMode, Protocol};
+//!
+//! // Quad I/O (1-4-4) read, with 3 byte addresses.
+//! const LUT: LookupTable = Protocol::new(IoMode::QuadIo, AddressBits::B24)
+//!     .read_dummy_cycles(6)
+//!     .lookup_table();
+//!
+//! assert_eq!(
+//!     LUT.get(Command::Read).to_string(),
+//!     "CMD_SDR SINGLE 0xEB\nRADDR_SDR QUAD 24 bits\nDUMMY_SDR QUAD 6 cycles\nREAD_SDR QUAD 0x04\nSTOP (x4)"
+//! );
+//! ```
+
+use crate::flexspi::{
+    AddressBits, Command, FlashPadType, Instr, LookupTable, Pads, Sequence, SequenceBuilder,
+};
+
+/// The I/O mode of a serial NOR device
+///
+/// The I/O mode selects the pads used for the command, the address, and the data.
+/// Unless the device is in QPI or OPI mode, commands other than the read are always
+/// sent on one pad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoMode {
+    /// 1-1-1
+    Single,
+    /// 1-1-2
+    DualOutput,
+    /// 1-2-2
+    DualIo,
+    /// 1-1-4
+    QuadOutput,
+    /// 1-4-4
+    QuadIo,
+    /// 4-4-4
+    Qpi,
+    /// 8-8-8
+    Opi,
+}
+
+impl IoMode {
+    /// Returns the pads for the command, the address, and the data of a read
+    const fn read_pads(self) -> (Pads, Pads, Pads) {
+        match self {
+            IoMode::Single => (Pads::One, Pads::One, Pads::One),
+            IoMode::DualOutput => (Pads::One, Pads::One, Pads::Two),
+            IoMode::DualIo => (Pads::One, Pads::Two, Pads::Two),
+            IoMode::QuadOutput => (Pads::One, Pads::One, Pads::Four),
+            IoMode::QuadIo => (Pads::One, Pads::Four, Pads::Four),
+            IoMode::Qpi => (Pads::Four, Pads::Four, Pads::Four),
+            IoMode::Opi => (Pads::Eight, Pads::Eight, Pads::Eight),
+        }
+    }
+    /// Returns the pads used by every command other than the read
+    const fn command_pads(self) -> Pads {
+        self.read_pads().0
+    }
+    /// Returns the flash pad type for a FlexSPI configuration block
+    pub const fn flash_pad_type(self) -> FlashPadType {
+        match self.read_pads().2 {
+            Pads::One => FlashPadType::Single,
+            Pads::Two => FlashPadType::Dual,
+            Pads::Four => FlashPadType::Quad,
+            Pads::Eight => FlashPadType::Octal,
+        }
+    }
+}
+
+/// The data rate of a serial NOR device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataRate {
+    /// Single transfer rate
+    Sdr,
+    /// Double transfer rate
+    ///
+    /// In the 1-x-x modes, only the read uses DTR. In QPI and OPI modes, all
+    /// commands use DTR. The 1-1-2 and 1-1-4 modes don't support DTR.
+    Dtr,
+}
+
+/// How the device expects each command byte
+///
+/// OPI devices usually expect a second command byte, which is either the same
+/// byte, or its inverse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandExtension {
+    /// Only send the command byte
+    OneByte,
+    /// Send the command byte twice
+    Repeat,
+    /// Send the command byte, then its inverse
+    Invert,
+}
+
+/// A JEDEC serial NOR protocol description
+///
+/// `Protocol::new` selects the JEDEC opcodes for the I/O mode, data rate and
+/// address width. For 4 byte addresses, the defaults are the 4 byte address
+/// opcodes, like `0xEC` and `0x12`. Override any opcode that your device doesn't
+/// support. The read dummy cycles also have a default for the I/O mode, but you
+/// should check it against your device and serial clock.
+///
+/// The generated lookup table assigns `Read`, `ReadStatus`, `WriteEnable`,
+/// `EraseSector`, `EraseBlock`, `PageProgram` and `ChipErase`. Like NXP's OPI
+/// lookup tables, an OPI protocol puts SPI (1-1-1) sequences in `ReadStatus` and
+/// `WriteEnable`, and the OPI sequences in `ReadStatusXpi` and `WriteEnableXpi`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Protocol {
+    io_mode: IoMode,
+    address_bits: AddressBits,
+    data_rate: DataRate,
+    command_extension: Option<CommandExtension>,
+    read_dummy_cycles: Option<u8>,
+    read: Option<u8>,
+    read_status: u8,
+    write_enable: u8,
+    sector_erase: Option<u8>,
+    block_erase: Option<u8>,
+    page_program: Option<u8>,
+    chip_erase: u8,
+}
+
+impl Protocol {
+    /// Describe a SDR protocol with the default JEDEC opcodes
+    pub const fn new(io_mode: IoMode, address_bits: AddressBits) -> Self {
+        Protocol {
+            io_mode,
+            address_bits,
+            data_rate: DataRate::Sdr,
+            command_extension: None,
+            read_dummy_cycles: None,
+            read: None,
+            read_status: 0x05,
+            write_enable: 0x06,
+            sector_erase: None,
+            block_erase: None,
+            page_program: None,
+            chip_erase: 0x60,
+        }
+    }
+    /// Set the data rate
+    pub const fn data_rate(mut self, data_rate: DataRate) -> Self {
+        self.data_rate = data_rate;
+        self
+    }
+    /// Set the command extension
+    ///
+    /// The default is [`CommandExtension::Invert`] for OPI devices, and
+    /// [`CommandExtension::OneByte`] for all others.
+    pub const fn command_extension(mut self, command_extension: CommandExtension) -> Self {
+        self.command_extension = Some(command_extension);
+        self
+    }
+    /// Set the `DUMMY` operand for the read
+    ///
+    /// OPI devices also use the dummy cycles to read the status register. A value of
+    /// zero removes the `DUMMY` instruction. Use
+    /// [`dummy_operand`](super::devices::dummy_operand) to compute the operand from a
+    /// device's dummy cycles.
+    pub const fn read_dummy_cycles(mut self, read_dummy_cycles: u8) -> Self {
+        self.read_dummy_cycles = Some(read_dummy_cycles);
+        self
+    }
+    /// Set the read opcode
+    pub const fn read_opcode(mut self, opcode: u8) -> Self {
+        self.read = Some(opcode);
+        self
+    }
+    /// Set the read status register opcode
+    pub const fn read_status_opcode(mut self, opcode: u8) -> Self {
+        self.read_status = opcode;
+        self
+    }
+    /// Set the write enable opcode
+    pub const fn write_enable_opcode(mut self, opcode: u8) -> Self {
+        self.write_enable = opcode;
+        self
+    }
+    /// Set the sector erase opcode
+    pub const fn sector_erase_opcode(mut self, opcode: u8) -> Self {
+        self.sector_erase = Some(opcode);
+        self
+    }
+    /// Set the block erase opcode
+    pub const fn block_erase_opcode(mut self, opcode: u8) -> Self {
+        self.block_erase = Some(opcode);
+        self
+    }
+    /// Set the page program opcode
+    pub const fn page_program_opcode(mut self, opcode: u8) -> Self {
+        self.page_program = Some(opcode);
+        self
+    }
+    /// Set the chip erase opcode
+    pub const fn chip_erase_opcode(mut self, opcode: u8) -> Self {
+        self.chip_erase = opcode;
+        self
+    }
+    /// Returns the I/O mode
+    pub const fn io_mode(&self) -> IoMode {
+        self.io_mode
+    }
+    /// Returns `true` if any instruction uses DTR
+    pub const fn is_dtr(&self) -> bool {
+        matches!(self.data_rate, DataRate::Dtr)
+    }
+    /// Generate the lookup table
+    ///
+    /// If the protocol uses DTR with the 1-1-2 or 1-1-4 I/O modes, you'll observe
+    /// a compile-time error.
+    pub const fn lookup_table(&self) -> LookupTable {
+        if self.is_dtr() && matches!(self.io_mode, IoMode::DualOutput | IoMode::QuadOutput) {
+            panic!("The 1-1-2 and 1-1-4 I/O modes don't support DTR");
+        }
+        let lut = if matches!(self.io_mode, IoMode::Opi) {
+            LookupTable::new()
+                .command(
+                    Command::ReadStatus,
+                    SequenceBuilder::new()
+                        .instr(Instr::cmd(Pads::One, self.read_status))
+                        .instr(Instr::read(Pads::One))
+                        .build(),
+                )
+                .command(Command::ReadStatusXpi, self.read_status_sequence())
+                .command(
+                    Command::WriteEnable,
+                    SequenceBuilder::new()
+                        .instr(Instr::cmd(Pads::One, self.write_enable))
+                        .build(),
+                )
+                .command(
+                    Command::WriteEnableXpi,
+                    self.command(self.write_enable).build(),
+                )
+        } else {
+            LookupTable::new()
+                .command(Command::ReadStatus, self.read_status_sequence())
+                .command(
+                    Command::WriteEnable,
+                    self.command(self.write_enable).build(),
+                )
+        };
+        lut.command(Command::Read, self.read_sequence())
+            .command(
+                Command::EraseSector,
+                self.addressed(self.opcode(self.sector_erase, 0x20, 0x21))
+                    .build(),
+            )
+            .command(
+                Command::EraseBlock,
+                self.addressed(self.opcode(self.block_erase, 0xD8, 0xDC))
+                    .build(),
+            )
+            .command(
+                Command::PageProgram,
+                self.addressed(self.opcode(self.page_program, 0x02, 0x12))
+                    .instr(if self.command_ddr() {
+                        Instr::write_ddr(self.io_mode.command_pads())
+                    } else {
+                        Instr::write(self.io_mode.command_pads())
+                    })
+                    .build(),
+            )
+            .command(Command::ChipErase, self.command(self.chip_erase).build())
+    }
+
+    /// Returns `true` if the commands other than the read use DTR
+    const fn command_ddr(&self) -> bool {
+        self.is_dtr() && matches!(self.io_mode, IoMode::Qpi | IoMode::Opi)
+    }
+    /// Returns `opcode`, or the 3 or 4 byte address default
+    const fn opcode(&self, opcode: Option<u8>, three_byte: u8, four_byte: u8) -> u8 {
+        match (opcode, self.address_bits) {
+            (Some(opcode), _) => opcode,
+            (None, AddressBits::B32) => four_byte,
+            (None, _) => three_byte,
+        }
+    }
+    const fn read_opcode_or_default(&self) -> u8 {
+        // There are no DTR opcodes for the 1-1-2 and 1-1-4 reads. lookup_table
+        // rejects those protocols.
+        let (sdr, dtr) = match self.io_mode {
+            IoMode::Single => (0x0B, 0x0D),
+            IoMode::DualOutput => (0x3B, 0x3B),
+            IoMode::DualIo => (0xBB, 0xBD),
+            IoMode::QuadOutput => (0x6B, 0x6B),
+            IoMode::QuadIo | IoMode::Qpi => (0xEB, 0xED),
+            IoMode::Opi => (0xEC, 0xEE),
+        };
+        // The 4 byte address opcodes follow their 3 byte address opcodes. OPI
+        // opcodes always take 4 byte addresses.
+        let offset = match (self.io_mode, self.address_bits) {
+            (IoMode::Opi, _) => 0,
+            (_, AddressBits::B32) => 1,
+            _ => 0,
+        };
+        match (self.read, self.is_dtr()) {
+            (Some(opcode), _) => opcode,
+            (None, false) => sdr + offset,
+            (None, true) => dtr + offset,
+        }
+    }
+    const fn read_dummy_cycles_or_default(&self) -> u8 {
+        match (self.read_dummy_cycles, self.io_mode) {
+            (Some(cycles), _) => cycles,
+            (None, IoMode::DualIo) => 4,
+            (None, IoMode::QuadIo | IoMode::Qpi) => 6,
+            (None, IoMode::Opi) => 20,
+            (None, _) => 8,
+        }
+    }
+    /// Returns a builder holding the command, and its extension
+    const fn command(&self, opcode: u8) -> SequenceBuilder {
+        let pads = self.io_mode.command_pads();
+        let ddr = self.command_ddr();
+        let builder = SequenceBuilder::new().instr(cmd(pads, opcode, ddr));
+        let extension = match self.command_extension {
+            Some(extension) => extension,
+            None if matches!(self.io_mode, IoMode::Opi) => CommandExtension::Invert,
+            None => CommandExtension::OneByte,
+        };
+        match extension {
+            CommandExtension::OneByte => builder,
+            CommandExtension::Repeat => builder.instr(cmd(pads, opcode, ddr)),
+            CommandExtension::Invert => builder.instr(cmd(pads, !opcode, ddr)),
+        }
+    }
+    /// Returns a builder holding the command, and a row address on the command pads
+    const fn addressed(&self, opcode: u8) -> SequenceBuilder {
+        self.command(opcode).instr(raddr(
+            self.io_mode.command_pads(),
+            self.address_bits,
+            self.command_ddr(),
+        ))
+    }
+    const fn read_sequence(&self) -> Sequence {
+        let (_, address_pads, data_pads) = self.io_mode.read_pads();
+        let ddr = self.is_dtr();
+        let mut builder = self.command(self.read_opcode_or_default()).instr(raddr(
+            address_pads,
+            self.address_bits,
+            ddr,
+        ));
+        let cycles = self.read_dummy_cycles_or_default();
+        if cycles > 0 {
+            builder = builder.instr(dummy(data_pads, cycles, ddr));
+        }
+        builder.instr(read(data_pads, ddr)).build()
+    }
+    const fn read_status_sequence(&self) -> Sequence {
+        let pads = self.io_mode.command_pads();
+        let ddr = self.command_ddr();
+        let mut builder = self.command(self.read_status);
+        if matches!(self.io_mode, IoMode::Opi) {
+            builder = builder.instr(raddr(pads, self.address_bits, ddr));
+            let cycles = self.read_dummy_cycles_or_default();
+            if cycles > 0 {
+                builder = builder.instr(dummy(pads, cycles, ddr));
+            }
+        }
+        builder.instr(read(pads, ddr)).build()
+    }
+}
+
+const fn cmd(pads: Pads, opcode: u8, ddr: bool) -> Instr {
+    if ddr {
+        Instr::cmd_ddr(pads, opcode)
+    } else {
+        Instr::cmd(pads, opcode)
+    }
+}
+
+const fn raddr(pads: Pads, bits: AddressBits, ddr: bool) -> Instr {
+    if ddr {
+        Instr::raddr_ddr(pads, bits)
+    } else {
+        Instr::raddr(pads, bits)
+    }
+}
+
+const fn dummy(pads: Pads, cycles: u8, ddr: bool) -> Instr {
+    if ddr {
+        Instr::dummy_ddr(pads, cycles)
+    } else {
+        Instr::dummy(pads, cycles)
+    }
+}
+
+const fn read(pads: Pads, ddr: bool) -> Instr {
+    if ddr {
+        Instr::read_ddr(pads)
+    } else {
+        Instr::read(pads)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CommandExtension, DataRate, IoMode, Protocol};
+    use crate::flexspi::{AddressBits, Command, FlashPadType, Instr, Pads, SequenceBuilder};
+
+    #[test]
+    fn dual_output_four_byte() {
+        const PROTOCOL: Protocol = Protocol::new(IoMode::DualOutput, AddressBits::B32);
+        let lut = PROTOCOL.lookup_table();
+        assert_eq!(
+            lut.get(Command::Read),
+            &SequenceBuilder::new()
+                .instr(Instr::cmd(Pads::One, 0x3C))
+                .instr(Instr::raddr(Pads::One, AddressBits::B32))
+                .instr(Instr::dummy(Pads::Two, 8))
+                .instr(Instr::read(Pads::Two))
+                .build()
+        );
+        assert_eq!(
+            lut.get(Command::EraseSector),
+            &SequenceBuilder::new()
+                .instr(Instr::cmd(Pads::One, 0x21))
+                .instr(Instr::raddr(Pads::One, AddressBits::B32))
+                .build()
+        );
+        assert_eq!(PROTOCOL.io_mode().flash_pad_type(), FlashPadType::Dual);
+    }
+
+    #[test]
+    fn quad_io_dtr() {
+        const PROTOCOL: Protocol = Protocol::new(IoMode::QuadIo, AddressBits::B24)
+            .data_rate(DataRate::Dtr)
+            .read_dummy_cycles(0);
+        let lut = PROTOCOL.lookup_table();
+        assert_eq!(
+            lut.get(Command::Read),
+            &SequenceBuilder::new()
+                .instr(Instr::cmd(Pads::One, 0xED))
+                .instr(Instr::raddr_ddr(Pads::Four, AddressBits::B24))
+                .instr(Instr::read_ddr(Pads::Four))
+                .build()
+        );
+        // Only the read uses DTR.
+        assert_eq!(
+            lut.get(Command::WriteEnable),
+            &SequenceBuilder::new()
+                .instr(Instr::cmd(Pads::One, 0x06))
+                .build()
+        );
+    }
+
+    #[test]
+    fn qpi_repeat() {
+        const PROTOCOL: Protocol = Protocol::new(IoMode::Qpi, AddressBits::B24)
+            .command_extension(CommandExtension::Repeat)
+            .page_program_opcode(0x32);
+        let lut = PROTOCOL.lookup_table();
+        assert_eq!(
+            lut.get(Command::PageProgram),
+            &SequenceBuilder::new()
+                .instr(Instr::cmd(Pads::Four, 0x32))
+                .instr(Instr::cmd(Pads::Four, 0x32))
+                .instr(Instr::raddr(Pads::Four, AddressBits::B24))
+                .instr(Instr::write(Pads::Four))
+                .build()
+        );
+        assert_eq!(
+            lut.get(Command::ReadStatus),
+            &SequenceBuilder::new()
+                .instr(Instr::cmd(Pads::Four, 0x05))
+                .instr(Instr::cmd(Pads::Four, 0x05))
+                .instr(Instr::read(Pads::Four))
+                .build()
+        );
+    }
+}
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{AddressBits, LookupTable};
+/// use imxrt_boot_gen::serial_flash::jedec::{DataRate, IoMode, Protocol};
+/// const LUT: LookupTable = Protocol::new(IoMode::QuadIo, AddressBits::B24)
+///     .data_rate(DataRate::Dtr)
+///     .lookup_table();
+/// ```
+#[cfg(doctest)]
+struct DtrWithQuadIo;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{AddressBits, LookupTable};
+/// use imxrt_boot_gen::serial_flash::jedec::{DataRate, IoMode, Protocol};
+/// const LUT: LookupTable = Protocol::new(IoMode::QuadOutput, AddressBits::B24)
+///     .data_rate(DataRate::Dtr)
+///     .lookup_table(); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct DtrWithQuadOutput;
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{AddressBits, LookupTable};
+/// use imxrt_boot_gen::serial_flash::jedec::{DataRate, IoMode, Protocol};
+/// const LUT: LookupTable = Protocol::new(IoMode::DualIo, AddressBits::B24)
+///     .data_rate(DataRate::Dtr)
+///     .lookup_table();
+/// ```
+#[cfg(doctest)]
+struct DtrWithDualIo;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{AddressBits, LookupTable};
+/// use imxrt_boot_gen::serial_flash::jedec::{DataRate, IoMode, Protocol};
+/// const LUT: LookupTable = Protocol::new(IoMode::DualOutput, AddressBits::B24)
+///     .data_rate(DataRate::Dtr)
+///     .lookup_table(); // <------- THIS SHOULD FAIL
+/// ```
+#[cfg(doctest)]
+struct DtrWithDualOutput;
@@ -0,0 +1,368 @@
+//! Serial NAND configuration blocks and fields
+
+use crate::bytes::{read_array, read_u16, read_u32, write_array, write_u16, write_u32};
+use crate::flexspi::{self, DecodeError};
+
+pub use super::nor::SerialClockFrequency;
+
+/// ECC check configuration for the serial NAND configuration block
+///
+/// Describes both the `eccCheckCustomEnable` field, and the `eccStatusMask`
+/// and `eccFailureMask` fields, which are only valid if the custom check
+/// is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EccCheck {
+    /// Use the common ECC check command and ECC masks
+    Common,
+    /// Use the ECC masks provided in the configuration block
+    Custom {
+        /// `eccStatusMask`
+        status_mask: u32,
+        /// `eccFailureMask`
+        failure_mask: u32,
+    },
+}
+
+#[allow(clippy::derivable_impls)] // #[default] on enum variants requires Rust 1.62
+impl Default for EccCheck {
+    fn default() -> Self {
+        EccCheck::Common
+    }
+}
+
+/// A serial NAND configuration block
+///
+/// This is the memory that you'll need to properly place in memory in order to
+/// boot your i.MX RT system from serial NAND. Consider keeping the symbol name, and specifying
+/// a link section, so that you can more easily place the memory in your linker
+/// script.
+///
+/// Unless otherwise specified, all unset fields are set to a bitpattern of zero.
+///
+/// ```no_run
+/// use imxrt_boot_gen::serial_flash::nand;
+/// # use imxrt_boot_gen::flexspi::{self, LookupTable};
+///
+/// # const FLEXSPI_CONFIGURATION_BLOCK: flexspi::ConfigurationBlock = flexspi::ConfigurationBlock::new(LookupTable::new());
+/// #[no_mangle]
+/// #[link_section = ".serial_nand_cb"]
+/// static SERIAL_NAND_CONFIGURATION_BLOCK: nand::ConfigurationBlock =
+///     nand::ConfigurationBlock::new(FLEXSPI_CONFIGURATION_BLOCK)
+///         .page_data_size(2048)
+///         .page_total_size(4096)
+///         .pages_per_block(64)
+///         .blocks_per_device(1024)
+///         .ecc_check(nand::EccCheck::Custom {
+///             status_mask: 0x30,
+///             failure_mask: 0x20,
+///         })
+///         .ip_cmd_serial_clk_freq(nand::SerialClockFrequency::MHz30);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C, packed)]
+pub struct ConfigurationBlock {
+    mem_cfg: flexspi::ConfigurationBlock,
+    page_data_size: u32,
+    page_total_size: u32,
+    pages_per_block: u32,
+    bypass_read_status: u8,
+    bypass_ecc_read: u8,
+    has_multi_planes: u8,
+    _reserved0: [u8; 1], // 0x1CF
+    ecc_check_custom_enable: u8,
+    ip_cmd_serial_clk_freq: u8,
+    read_page_time_us: u16,
+    ecc_status_mask: u32,
+    ecc_failure_mask: u32,
+    blocks_per_device: u32,
+    _reserved1: [u8; 32],
+}
+
+impl ConfigurationBlock {
+    /// Create a new serial NAND configuration block based on the FlexSPI configuration
+    /// block
+    pub const fn new(mut mem_cfg: flexspi::ConfigurationBlock) -> Self {
+        mem_cfg.device_type = 2;
+        ConfigurationBlock {
+            mem_cfg,
+            page_data_size: 0,
+            page_total_size: 0,
+            pages_per_block: 0,
+            bypass_read_status: 0,
+            bypass_ecc_read: 0,
+            has_multi_planes: 0,
+            _reserved0: [0; 1],
+            ecc_check_custom_enable: 0,
+            ip_cmd_serial_clk_freq: 0,
+            read_page_time_us: 0,
+            ecc_status_mask: 0,
+            ecc_failure_mask: 0,
+            blocks_per_device: 0,
+            _reserved1: [0; 32],
+        }
+    }
+    /// Set the page data size, in bytes (`pageDataSize`)
+    ///
+    /// This is usually 2048 or 4096.
+    pub const fn page_data_size(mut self, page_data_size: u32) -> Self {
+        self.page_data_size = page_data_size;
+        self
+    }
+    /// Set the total page size, in bytes (`pageTotalSize`)
+    ///
+    /// This is the page data size plus the out-of-band area. It equals
+    /// 2 to the power of the column address width.
+    pub const fn page_total_size(mut self, page_total_size: u32) -> Self {
+        self.page_total_size = page_total_size;
+        self
+    }
+    /// Set the number of pages in one block (`pagesPerBlock`)
+    pub const fn pages_per_block(mut self, pages_per_block: u32) -> Self {
+        self.pages_per_block = pages_per_block;
+        self
+    }
+    /// Set the number of blocks in the serial NAND device (`blocksPerDevice`)
+    pub const fn blocks_per_device(mut self, blocks_per_device: u32) -> Self {
+        self.blocks_per_device = blocks_per_device;
+        self
+    }
+    /// Indicate that the device has two planes (`hasMultiPlanes`)
+    ///
+    /// If not set, the device is assumed to have one plane.
+    pub const fn has_multi_planes(mut self, has_multi_planes: bool) -> Self {
+        self.has_multi_planes = has_multi_planes as u8;
+        self
+    }
+    /// Bypass the read status register (`bypassReadStatus`)
+    ///
+    /// When bypassed, the ROM waits [`read_page_time_us`](ConfigurationBlock::read_page_time_us)
+    /// during a page read instead of polling the status register.
+    pub const fn bypass_read_status(mut self, bypass_read_status: bool) -> Self {
+        self.bypass_read_status = bypass_read_status as u8;
+        self
+    }
+    /// Bypass the ECC read (`bypassEccRead`)
+    pub const fn bypass_ecc_read(mut self, bypass_ecc_read: bool) -> Self {
+        self.bypass_ecc_read = bypass_ecc_read as u8;
+        self
+    }
+    /// Set the wait time during a page read, in microseconds (`readPageTimeUs`)
+    ///
+    /// Only effective if the read status is bypassed.
+    pub const fn read_page_time_us(mut self, read_page_time_us: u16) -> Self {
+        self.read_page_time_us = read_page_time_us;
+        self
+    }
+    /// Sets the ECC check configuration. The `EccCheck::Common` variant
+    /// will set `eccCheckCustomEnable` to "disabled". Otherwise, we set
+    /// `eccCheckCustomEnable` to "enabled," and we use the ECC masks in the
+    /// configuration block.
+    ///
+    /// If not set, this defaults to `EccCheck::Common`.
+    pub const fn ecc_check(mut self, ecc_check: EccCheck) -> Self {
+        match ecc_check {
+            EccCheck::Common => {
+                self.ecc_check_custom_enable = 0;
+            }
+            EccCheck::Custom {
+                status_mask,
+                failure_mask,
+            } => {
+                self.ecc_check_custom_enable = 1;
+                self.ecc_status_mask = status_mask;
+                self.ecc_failure_mask = failure_mask;
+            }
+        }
+        self
+    }
+    /// Set the serial clock frequency
+    pub const fn ip_cmd_serial_clk_freq(
+        mut self,
+        serial_clock_frequency: SerialClockFrequency,
+    ) -> Self {
+        self.ip_cmd_serial_clk_freq = serial_clock_frequency as u8;
+        self
+    }
+
+    /// Returns the FlexSPI configuration block
+    pub const fn get_mem_cfg(&self) -> flexspi::ConfigurationBlock {
+        self.mem_cfg
+    }
+    /// Returns the page data size, in bytes (`pageDataSize`)
+    pub const fn get_page_data_size(&self) -> u32 {
+        self.page_data_size
+    }
+    /// Returns the total page size, in bytes (`pageTotalSize`)
+    pub const fn get_page_total_size(&self) -> u32 {
+        self.page_total_size
+    }
+    /// Returns the number of pages in one block (`pagesPerBlock`)
+    pub const fn get_pages_per_block(&self) -> u32 {
+        self.pages_per_block
+    }
+    /// Returns the number of blocks in the serial NAND device (`blocksPerDevice`)
+    pub const fn get_blocks_per_device(&self) -> u32 {
+        self.blocks_per_device
+    }
+    /// Returns `true` if the device has two planes (`hasMultiPlanes`)
+    pub const fn get_has_multi_planes(&self) -> bool {
+        self.has_multi_planes != 0
+    }
+    /// Returns `true` if the read status register is bypassed (`bypassReadStatus`)
+    pub const fn get_bypass_read_status(&self) -> bool {
+        self.bypass_read_status != 0
+    }
+    /// Returns `true` if the ECC read is bypassed (`bypassEccRead`)
+    pub const fn get_bypass_ecc_read(&self) -> bool {
+        self.bypass_ecc_read != 0
+    }
+    /// Returns the wait time during a page read, in microseconds (`readPageTimeUs`)
+    pub const fn get_read_page_time_us(&self) -> u16 {
+        self.read_page_time_us
+    }
+    /// Returns the ECC check configuration
+    pub const fn get_ecc_check(&self) -> EccCheck {
+        if self.ecc_check_custom_enable == 0 {
+            EccCheck::Common
+        } else {
+            EccCheck::Custom {
+                status_mask: self.ecc_status_mask,
+                failure_mask: self.ecc_failure_mask,
+            }
+        }
+    }
+    /// Returns the serial clock frequency
+    pub const fn get_ip_cmd_serial_clk_freq(&self) -> SerialClockFrequency {
+        match SerialClockFrequency::from_raw(self.ip_cmd_serial_clk_freq) {
+            Some(serial_clock_frequency) => serial_clock_frequency,
+            // Unreachable; the value is always set from, or checked against, the enum.
+            None => SerialClockFrequency::NoChange,
+        }
+    }
+
+    /// Returns the memory representation of the serial NAND configuration block
+    ///
+    /// Multi-byte fields are always little endian, regardless of the host's
+    /// endianness. Use this to write the configuration block into a boot image.
+    pub const fn to_bytes(&self) -> [u8; 512] {
+        let mem_cfg = self.mem_cfg;
+        let mut bytes = mem_cfg.encode([0; 512]);
+        bytes = write_u32(bytes, 0x1C0, self.page_data_size);
+        bytes = write_u32(bytes, 0x1C4, self.page_total_size);
+        bytes = write_u32(bytes, 0x1C8, self.pages_per_block);
+        bytes[0x1CC] = self.bypass_read_status;
+        bytes[0x1CD] = self.bypass_ecc_read;
+        bytes[0x1CE] = self.has_multi_planes;
+        bytes = write_array(bytes, 0x1CF, &self._reserved0);
+        bytes[0x1D0] = self.ecc_check_custom_enable;
+        bytes[0x1D1] = self.ip_cmd_serial_clk_freq;
+        bytes = write_u16(bytes, 0x1D2, self.read_page_time_us);
+        bytes = write_u32(bytes, 0x1D4, self.ecc_status_mask);
+        bytes = write_u32(bytes, 0x1D8, self.ecc_failure_mask);
+        bytes = write_u32(bytes, 0x1DC, self.blocks_per_device);
+        write_array(bytes, 0x1E0, &self._reserved1)
+    }
+
+    /// Decode a serial NAND configuration block from its memory representation
+    ///
+    /// `from_bytes` decodes the FlexSPI configuration block, then checks that the
+    /// device type is serial NAND. See
+    /// [`flexspi::ConfigurationBlock::from_bytes`] for more information.
+    pub const fn from_bytes(bytes: &[u8; 512]) -> Result<Self, DecodeError> {
+        let mem_cfg = match flexspi::ConfigurationBlock::decode(bytes) {
+            Ok(mem_cfg) => mem_cfg,
+            Err(err) => return Err(err),
+        };
+        if mem_cfg.device_type != 2 {
+            return Err(DecodeError::DeviceType(mem_cfg.device_type));
+        }
+        let ip_cmd_serial_clk_freq = bytes[0x1D1];
+        if SerialClockFrequency::from_raw(ip_cmd_serial_clk_freq).is_none() {
+            return Err(DecodeError::IpCmdSerialClockFrequency(
+                ip_cmd_serial_clk_freq as u32,
+            ));
+        }
+        Ok(ConfigurationBlock {
+            mem_cfg,
+            page_data_size: read_u32(bytes, 0x1C0),
+            page_total_size: read_u32(bytes, 0x1C4),
+            pages_per_block: read_u32(bytes, 0x1C8),
+            bypass_read_status: bytes[0x1CC],
+            bypass_ecc_read: bytes[0x1CD],
+            has_multi_planes: bytes[0x1CE],
+            _reserved0: read_array(bytes, 0x1CF),
+            ecc_check_custom_enable: bytes[0x1D0],
+            ip_cmd_serial_clk_freq,
+            read_page_time_us: read_u16(bytes, 0x1D2),
+            ecc_status_mask: read_u32(bytes, 0x1D4),
+            ecc_failure_mask: read_u32(bytes, 0x1D8),
+            blocks_per_device: read_u32(bytes, 0x1DC),
+            _reserved1: read_array(bytes, 0x1E0),
+        })
+    }
+}
+
+const _STATIC_ASSERT_SIZE: [u32; 1] =
+    [0; (core::mem::size_of::<ConfigurationBlock>() == 512) as usize];
+
+#[cfg(test)]
+mod test {
+    use super::{flexspi, ConfigurationBlock, DecodeError, EccCheck, SerialClockFrequency};
+    use crate::flexspi::LookupTable;
+
+    #[test]
+    fn smoke() {
+        const _CFG: ConfigurationBlock =
+            ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+                .page_data_size(2048)
+                .page_total_size(4096)
+                .pages_per_block(64)
+                .blocks_per_device(1024)
+                .has_multi_planes(false)
+                .bypass_read_status(true)
+                .read_page_time_us(100)
+                .bypass_ecc_read(false)
+                .ecc_check(EccCheck::Custom {
+                    status_mask: 0x30,
+                    failure_mask: 0x20,
+                })
+                .ip_cmd_serial_clk_freq(SerialClockFrequency::MHz30);
+    }
+
+    #[test]
+    fn device_type() {
+        const CFG: ConfigurationBlock =
+            ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+        let device_type = CFG.mem_cfg.device_type;
+        assert_eq!(device_type, 2);
+    }
+
+    #[test]
+    fn decode() {
+        const CFG: ConfigurationBlock =
+            ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()))
+                .page_data_size(2048)
+                .page_total_size(4096)
+                .pages_per_block(64)
+                .ecc_check(EccCheck::Custom {
+                    status_mask: 0x30,
+                    failure_mask: 0x20,
+                });
+        let mut bytes = CFG.to_bytes();
+        let decoded = ConfigurationBlock::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, CFG);
+        assert_eq!(decoded.get_page_total_size(), 4096);
+        assert_eq!(
+            decoded.get_ecc_check(),
+            EccCheck::Custom {
+                status_mask: 0x30,
+                failure_mask: 0x20,
+            }
+        );
+        bytes[0x1D1] = 0xFF;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&bytes),
+            Err(DecodeError::IpCmdSerialClockFrequency(0xFF))
+        );
+    }
+}
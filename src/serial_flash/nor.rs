@@ -1,6 +1,9 @@
 //! Serial NOR configuration blocks and fields
 
-use crate::flexspi;
+use core::fmt;
+
+use crate::bytes::{read_array, read_u32, write_array, write_u32};
+use crate::flexspi::{self, Command, DecodeError, LutError, MAX_BUSY_OFFSET};
 
 /// `ipCmdSerialClkFreq` field for serial NOR-specific FCB
 ///
@@ -24,6 +27,102 @@ pub enum SerialClockFrequency {
     MHz166,
 }
 
+impl SerialClockFrequency {
+    pub(crate) const fn from_raw(raw: u8) -> Option<Self> {
+        use SerialClockFrequency::*;
+        match raw {
+            raw if raw == NoChange as u8 => Some(NoChange),
+            raw if raw == MHz30 as u8 => Some(MHz30),
+            raw if raw == MHz50 as u8 => Some(MHz50),
+            raw if raw == MHz60 as u8 => Some(MHz60),
+            #[cfg(not(feature = "imxrt500"))]
+            raw if raw == MHz75 as u8 => Some(MHz75),
+            raw if raw == MHz80 as u8 => Some(MHz80),
+            raw if raw == MHz100 as u8 => Some(MHz100),
+            #[cfg(any(feature = "imxrt1060", feature = "imxrt1064", feature = "imxrt500"))]
+            raw if raw == MHz120 as u8 => Some(MHz120),
+            raw if raw == MHz133 as u8 => Some(MHz133),
+            #[cfg(any(feature = "imxrt1060", feature = "imxrt1064", feature = "imxrt500"))]
+            raw if raw == MHz166 as u8 => Some(MHz166),
+            _ => None,
+        }
+    }
+}
+
+/// A serial NOR configuration block validation error
+///
+/// See [`ConfigurationBlock::check`] and [`ConfigurationBlock::validate`] for more
+/// information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The lookup table has no [`Command::Read`] sequence
+    ReadSequence,
+    /// The serial flash A1 size is zero
+    FlashSizeA1Zero,
+    /// The serial flash A1 size is not a power of two
+    FlashSizeA1PowerOfTwo(u32),
+    /// The page size is zero
+    PageSizeZero,
+    /// The sector size is not a non-zero multiple of the page size
+    SectorSize {
+        /// The sector size
+        sector_size: u32,
+        /// The page size
+        page_size: u32,
+    },
+    /// The busy offset is not a bit in the 32 bit status register
+    BusyOffset(u16),
+    /// The lookup table disagrees with the FlexSPI configuration block
+    ///
+    /// See [`flexspi::ConfigurationBlock::check_lookup_table`] for more information.
+    LookupTable(LutError),
+}
+
+impl ValidationError {
+    /// Returns a message that describes the error
+    pub const fn message(self) -> &'static str {
+        match self {
+            ValidationError::ReadSequence => "The lookup table has no Read sequence",
+            ValidationError::FlashSizeA1Zero => "The serial flash A1 size is zero",
+            ValidationError::FlashSizeA1PowerOfTwo(_) => {
+                "The serial flash A1 size is not a power of two"
+            }
+            ValidationError::PageSizeZero => "The page size is zero",
+            ValidationError::SectorSize { .. } => {
+                "The sector size is not a non-zero multiple of the page size"
+            }
+            ValidationError::BusyOffset(_) => {
+                "The busy offset must be within the 32 bit status word"
+            }
+            ValidationError::LookupTable(err) => err.kind().message(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationError::FlashSizeA1PowerOfTwo(size) => {
+                write!(f, "{} ({})", self.message(), size)
+            }
+            ValidationError::SectorSize {
+                sector_size,
+                page_size,
+            } => write!(
+                f,
+                "{} (sector size {}, page size {})",
+                self.message(),
+                sector_size,
+                page_size
+            ),
+            ValidationError::BusyOffset(offset) => write!(f, "{} ({})", self.message(), offset),
+            ValidationError::LookupTable(err) => write!(f, "{}", err),
+            _ => f.write_str(self.message()),
+        }
+    }
+}
+
 /// A serial NOR configuration block
 ///
 /// This is the memory that you'll need to properly place in memory in order to
@@ -46,7 +145,7 @@ pub enum SerialClockFrequency {
 ///         .sector_size(4096)
 ///         .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30);
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(C, packed)]
 pub struct ConfigurationBlock {
     mem_cfg: flexspi::ConfigurationBlock,
@@ -87,6 +186,150 @@ impl ConfigurationBlock {
         self.ip_cmd_serial_clk_freq = serial_clock_frequency as u32;
         self
     }
+
+    /// Returns the FlexSPI configuration block
+    pub const fn get_mem_cfg(&self) -> flexspi::ConfigurationBlock {
+        self.mem_cfg
+    }
+    /// Returns the serial NOR page size
+    pub const fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+    /// Returns the serial NOR sector size
+    pub const fn get_sector_size(&self) -> u32 {
+        self.sector_size
+    }
+    /// Returns the serial clock frequency
+    pub const fn get_ip_cmd_serial_clk_freq(&self) -> SerialClockFrequency {
+        match SerialClockFrequency::from_raw(self.ip_cmd_serial_clk_freq as u8) {
+            Some(serial_clock_frequency) => serial_clock_frequency,
+            // Unreachable; the value is always set from, or checked against, the enum.
+            None => SerialClockFrequency::NoChange,
+        }
+    }
+
+    /// Check the configuration block for settings that prevent a boot
+    ///
+    /// `check` requires that
+    ///
+    /// - the lookup table has a [`Command::Read`] sequence.
+    /// - the serial flash A1 size is non-zero, and a power of two.
+    /// - the page size is non-zero.
+    /// - the sector size is a non-zero multiple of the page size.
+    /// - the busy offset is within the ROM's 32 bit status word.
+    /// - the lookup table agrees with the FlexSPI configuration block. See
+    ///   [`flexspi::ConfigurationBlock::check_lookup_table`] for those rules.
+    ///
+    /// Use `check` at runtime, like after [`from_bytes`](ConfigurationBlock::from_bytes).
+    /// Use [`validate`](ConfigurationBlock::validate) in a `const` context.
+    #[allow(clippy::manual_is_multiple_of)] // u32::is_multiple_of requires Rust 1.87
+    pub const fn check(&self) -> Result<(), ValidationError> {
+        let mem_cfg = self.mem_cfg;
+        if !mem_cfg.get_lookup_table().is_populated(Command::Read) {
+            return Err(ValidationError::ReadSequence);
+        }
+        let flash_size_a1 = mem_cfg.serial_flash_sizes[0];
+        if flash_size_a1 == 0 {
+            return Err(ValidationError::FlashSizeA1Zero);
+        }
+        if !flash_size_a1.is_power_of_two() {
+            return Err(ValidationError::FlashSizeA1PowerOfTwo(flash_size_a1));
+        }
+        let page_size = self.page_size;
+        if page_size == 0 {
+            return Err(ValidationError::PageSizeZero);
+        }
+        let sector_size = self.sector_size;
+        if sector_size == 0 || sector_size % page_size != 0 {
+            return Err(ValidationError::SectorSize {
+                sector_size,
+                page_size,
+            });
+        }
+        let busy_offset = mem_cfg.busy_offset;
+        if busy_offset > MAX_BUSY_OFFSET {
+            return Err(ValidationError::BusyOffset(busy_offset));
+        }
+        if let Err(err) = mem_cfg.check_lookup_table() {
+            return Err(ValidationError::LookupTable(err));
+        }
+        Ok(())
+    }
+
+    /// Validate the configuration block
+    ///
+    /// Call `validate` as the final step of your configuration block. If the block
+    /// fails any of the rules described in [`check`](ConfigurationBlock::check), you'll
+    /// observe a compile-time error.
+    ///
+    /// ```
+    /// use imxrt_boot_gen::flexspi::{self, *, opcodes::sdr::*};
+    /// use imxrt_boot_gen::serial_flash::nor;
+    ///
+    /// const LUT: LookupTable = LookupTable::new()
+    ///     .command(Command::Read, SequenceBuilder::new()
+    ///         .instr(Instr::new(CMD, Pads::One, 0x03))
+    ///         .instr(Instr::new(RADDR, Pads::One, 0x18))
+    ///         .instr(Instr::new(READ, Pads::One, 0x04))
+    ///         .build());
+    ///
+    /// const NOR_CB: nor::ConfigurationBlock = nor::ConfigurationBlock::new(
+    ///     flexspi::ConfigurationBlock::new(LUT)
+    ///         .flash_size(SerialFlashRegion::A1, 8 * 1024 * 1024),
+    /// )
+    /// .page_size(256)
+    /// .sector_size(4096)
+    /// .validate();
+    /// ```
+    pub const fn validate(self) -> Self {
+        match self.check() {
+            Ok(()) => self,
+            Err(err) => panic!("{}", err.message()),
+        }
+    }
+
+    /// Returns the memory representation of the serial NOR configuration block
+    ///
+    /// Multi-byte fields are always little endian, regardless of the host's
+    /// endianness. Use this to write the configuration block into a boot image.
+    pub const fn to_bytes(&self) -> [u8; 512] {
+        let mem_cfg = self.mem_cfg;
+        let mut bytes = mem_cfg.encode([0; 512]);
+        bytes = write_u32(bytes, 0x1C0, self.page_size);
+        bytes = write_u32(bytes, 0x1C4, self.sector_size);
+        bytes = write_u32(bytes, 0x1C8, self.ip_cmd_serial_clk_freq);
+        write_array(bytes, 0x1CC, &self._reserved)
+    }
+
+    /// Decode a serial NOR configuration block from its memory representation
+    ///
+    /// `from_bytes` decodes the FlexSPI configuration block, then checks that the
+    /// device type is serial NOR. See
+    /// [`flexspi::ConfigurationBlock::from_bytes`] for more information.
+    pub const fn from_bytes(bytes: &[u8; 512]) -> Result<Self, DecodeError> {
+        let mem_cfg = match flexspi::ConfigurationBlock::decode(bytes) {
+            Ok(mem_cfg) => mem_cfg,
+            Err(err) => return Err(err),
+        };
+        if mem_cfg.device_type != 1 {
+            return Err(DecodeError::DeviceType(mem_cfg.device_type));
+        }
+        let ip_cmd_serial_clk_freq = read_u32(bytes, 0x1C8);
+        if ip_cmd_serial_clk_freq > u8::MAX as u32
+            || SerialClockFrequency::from_raw(ip_cmd_serial_clk_freq as u8).is_none()
+        {
+            return Err(DecodeError::IpCmdSerialClockFrequency(
+                ip_cmd_serial_clk_freq,
+            ));
+        }
+        Ok(ConfigurationBlock {
+            mem_cfg,
+            page_size: read_u32(bytes, 0x1C0),
+            sector_size: read_u32(bytes, 0x1C4),
+            ip_cmd_serial_clk_freq,
+            _reserved: read_array(bytes, 0x1CC),
+        })
+    }
 }
 
 const _STATIC_ASSERT_SIZE: [u32; 1] =
@@ -94,8 +337,10 @@ const _STATIC_ASSERT_SIZE: [u32; 1] =
 
 #[cfg(test)]
 mod test {
-    use super::{flexspi, ConfigurationBlock, SerialClockFrequency};
-    use crate::flexspi::LookupTable;
+    use super::{flexspi, ConfigurationBlock, DecodeError, SerialClockFrequency, ValidationError};
+    use crate::flexspi::{
+        opcodes::sdr::*, Command, Instr, LookupTable, Pads, SequenceBuilder, SerialFlashRegion,
+    };
 
     #[test]
     fn smoke() {
@@ -106,6 +351,63 @@ mod test {
                 .ip_cmd_serial_clk_freq(SerialClockFrequency::MHz30);
     }
 
+    #[test]
+    fn decode_device_type() {
+        const CFG: ConfigurationBlock =
+            ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new()));
+        let mut bytes = CFG.to_bytes();
+        assert_eq!(ConfigurationBlock::from_bytes(&bytes), Ok(CFG));
+        bytes[0x044] = 2;
+        assert_eq!(
+            ConfigurationBlock::from_bytes(&bytes),
+            Err(DecodeError::DeviceType(2))
+        );
+    }
+
+    #[test]
+    fn check() {
+        const LUT: LookupTable = LookupTable::new().command(
+            Command::Read,
+            SequenceBuilder::new()
+                .instr(Instr::new(CMD, Pads::One, 0x03))
+                .build(),
+        );
+        const MEM_CFG: flexspi::ConfigurationBlock =
+            flexspi::ConfigurationBlock::new(LUT).flash_size(SerialFlashRegion::A1, 0x0080_0000);
+        const CFG: ConfigurationBlock = ConfigurationBlock::new(MEM_CFG)
+            .page_size(256)
+            .sector_size(4096)
+            .validate();
+        assert_eq!(CFG.check(), Ok(()));
+        assert_eq!(CFG.get_page_size(), 256);
+        assert_eq!(CFG.get_sector_size(), 4096);
+        assert_eq!(
+            CFG.get_mem_cfg().get_flash_size(SerialFlashRegion::A1),
+            0x0080_0000
+        );
+
+        assert_eq!(
+            ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LookupTable::new())).check(),
+            Err(ValidationError::ReadSequence)
+        );
+        assert_eq!(
+            ConfigurationBlock::new(flexspi::ConfigurationBlock::new(LUT)).check(),
+            Err(ValidationError::FlashSizeA1Zero)
+        );
+        assert_eq!(
+            ConfigurationBlock::new(MEM_CFG.flash_size(SerialFlashRegion::A1, 3 << 20)).check(),
+            Err(ValidationError::FlashSizeA1PowerOfTwo(3 << 20))
+        );
+        assert_eq!(CFG.page_size(0).check(), Err(ValidationError::PageSizeZero));
+        assert_eq!(
+            CFG.sector_size(4095).check(),
+            Err(ValidationError::SectorSize {
+                sector_size: 4095,
+                page_size: 256
+            })
+        );
+    }
+
     #[test]
     #[cfg(feature = "imxrt500")]
     fn serial_clk_freq() {
@@ -119,3 +421,40 @@ mod test {
         assert_eq!(SerialClockFrequency::MHz133 as u8, 7);
     }
 }
+
+//
+// Keep these two tests in sync
+//
+// The first one lets you know if the second one is failing to compile
+// in the way we expect.
+//
+
+/// ```
+/// use imxrt_boot_gen::flexspi::{self, *, opcodes::sdr::*};
+/// use imxrt_boot_gen::serial_flash::nor;
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::Read, SequenceBuilder::new().instr(Instr::new(CMD, Pads::One, 0x03)).build());
+/// const NOR_CB: nor::ConfigurationBlock = nor::ConfigurationBlock::new(
+///     flexspi::ConfigurationBlock::new(LUT).flash_size(SerialFlashRegion::A1, 0x0080_0000),
+/// )
+/// .page_size(256)
+/// .sector_size(4096)
+/// .validate();
+/// ```
+#[cfg(doctest)]
+struct ValidateSectorSize;
+
+/// ```compile_fail
+/// use imxrt_boot_gen::flexspi::{self, *, opcodes::sdr::*};
+/// use imxrt_boot_gen::serial_flash::nor;
+/// const LUT: LookupTable = LookupTable::new()
+///     .command(Command::Read, SequenceBuilder::new().instr(Instr::new(CMD, Pads::One, 0x03)).build());
+/// const NOR_CB: nor::ConfigurationBlock = nor::ConfigurationBlock::new(
+///     flexspi::ConfigurationBlock::new(LUT).flash_size(SerialFlashRegion::A1, 0x0080_0000),
+/// )
+/// .page_size(256)
+/// .sector_size(4095) // <------- THIS SHOULD FAIL
+/// .validate();
+/// ```
+#[cfg(doctest)]
+struct ValidateSectorSizeMismatch;
@@ -0,0 +1,913 @@
+//! Serial Flash Discoverable Parameters (SFDP, JESD216)
+//!
+//! Serial NOR devices describe themselves in their SFDP tables, which you read with
+//! the `0x5A` command. [`Sfdp::parse`] takes that dump, and derives the device
+//! size, page size, erase types, quad enable requirement, and the fastest 1-x-x
+//! read. Use the result to create a [`LookupTable`] and a [`nor::ConfigurationBlock`]
+//! for a device that isn't in the [`devices`](super::devices) module.
+//!
+//! The parser is a `const fn`, so you can parse a dump that you include in your
+//! program:
+//!
+//! ```ignore
+//! use imxrt_boot_gen::serial_flash::{nor, sfdp::Sfdp};
+//!
+//! const NOR_CB: nor::ConfigurationBlock = match Sfdp::parse(include_bytes!("sfdp.bin")) {
+//!     Ok(sfdp) => sfdp.configuration_block(),
+//!     Err(err) => panic!("{}", err.message()),
+//! };
+//! ```
+//!
+//! The parser reads the Basic Flash Parameter Table, the 4-byte Address Instruction
+//! Table, the Sector Map Parameter Table, and the xSPI Profile 1.0 table. The
+//! [`XspiProfile`] describes the device's 8D-8D-8D (octal DTR) read. The derived
+//! protocol and configuration block don't use it, since a configuration block can't
+//! switch a device into OPI mode.
+
+use core::fmt;
+
+use super::devices::{spi_configuration_block, QuadEnable, THREE_BYTE_ADDRESS_LIMIT};
+use super::jedec::{IoMode, Protocol};
+use super::nor;
+use crate::bytes::read_u32;
+use crate::flexspi::{AddressBits, LookupTable};
+
+/// ASCII 'SFDP'
+const SIGNATURE: u32 = 0x5044_4653;
+
+const HEADER_SIZE: usize = 8;
+const PARAMETER_HEADER_SIZE: usize = 8;
+
+/// Basic Flash Parameter Table
+const BASIC_TABLE_ID: u16 = 0xFF00;
+/// Sector Map Parameter Table
+const SECTOR_MAP_TABLE_ID: u16 = 0xFF81;
+/// 4-byte Address Instruction Table
+const FOUR_BYTE_ADDRESS_TABLE_ID: u16 = 0xFF84;
+/// xSPI Profile 1.0
+const XSPI_PROFILE_TABLE_ID: u16 = 0xFF05;
+
+/// The number of erase types in the Basic Flash Parameter Table
+pub const ERASE_TYPES: usize = 4;
+
+/// The page size of a device that doesn't report one
+const DEFAULT_PAGE_SIZE: u32 = 256;
+
+/// The serial clock frequencies, in MHz, of the xSPI Profile 1.0 dummy cycles
+const XSPI_FREQUENCIES_MHZ: [u16; 4] = [100, 133, 166, 200];
+
+/// An error that occurs when parsing SFDP
+///
+/// Each variant carries the unexpected value, if there is one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SfdpError {
+    /// The signature is not ASCII 'SFDP'
+    Signature(u32),
+    /// A header or table extends past the end of the dump
+    Truncated,
+    /// There's no Basic Flash Parameter Table, or it's shorter than nine DWORDs
+    MissingBasicTable,
+    /// The density is 4GiB or larger
+    Density(u32),
+    /// The quad enable requirement is reserved
+    QuadEnable(u8),
+    /// The device is larger than 16MiB, but there's no 4 byte address read, page
+    /// program, or erase
+    FourByteAddress,
+    /// The device has no erase types
+    EraseType,
+    /// The sector map is malformed
+    SectorMap,
+    /// An optional parameter table is shorter than the DWORDs that the parser reads
+    ///
+    /// Carries the table's parameter ID.
+    ShortTable(u16),
+}
+
+impl SfdpError {
+    /// Returns a description of the error
+    pub const fn message(self) -> &'static str {
+        match self {
+            SfdpError::Signature(_) => "The SFDP signature is invalid",
+            SfdpError::Truncated => "The SFDP dump is truncated",
+            SfdpError::MissingBasicTable => "The SFDP dump has no Basic Flash Parameter Table",
+            SfdpError::Density(_) => "The SFDP density is 4GiB or larger",
+            SfdpError::QuadEnable(_) => "The SFDP quad enable requirement is reserved",
+            SfdpError::FourByteAddress => {
+                "The device needs 4 byte addresses, but SFDP doesn't describe the instructions"
+            }
+            SfdpError::EraseType => "The SFDP dump has no erase types",
+            SfdpError::SectorMap => "The SFDP sector map is malformed",
+            SfdpError::ShortTable(_) => "A SFDP parameter table is too short",
+        }
+    }
+}
+
+impl fmt::Display for SfdpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message())?;
+        match *self {
+            SfdpError::Signature(raw) => write!(f, " ({:#010X})", raw),
+            SfdpError::Density(raw) => write!(f, " ({:#010X})", raw),
+            SfdpError::QuadEnable(raw) => write!(f, " ({:#05b})", raw),
+            SfdpError::ShortTable(id) => write!(f, " ({:#06X})", id),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The location of a SFDP parameter table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterTable {
+    id: u16,
+    major: u8,
+    minor: u8,
+    offset: usize,
+    dwords: usize,
+}
+
+impl ParameterTable {
+    /// Returns the parameter ID
+    pub const fn id(self) -> u16 {
+        self.id
+    }
+    /// Returns the major and minor revision
+    pub const fn revision(self) -> (u8, u8) {
+        (self.major, self.minor)
+    }
+    /// Returns the table's byte offset in the dump
+    pub const fn offset(self) -> usize {
+        self.offset
+    }
+    /// Returns the table's length, in DWORDs
+    pub const fn len(self) -> usize {
+        self.dwords
+    }
+    /// Returns `true` if the table has no DWORDs
+    pub const fn is_empty(self) -> bool {
+        self.dwords == 0
+    }
+}
+
+/// A fast read instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastRead {
+    io_mode: IoMode,
+    opcode: u8,
+    mode_clocks: u8,
+    dummy_clocks: u8,
+}
+
+impl FastRead {
+    /// Returns the I/O mode
+    pub const fn io_mode(self) -> IoMode {
+        self.io_mode
+    }
+    /// Returns the opcode
+    pub const fn opcode(self) -> u8 {
+        self.opcode
+    }
+    /// Returns the mode bit clocks
+    pub const fn mode_clocks(self) -> u8 {
+        self.mode_clocks
+    }
+    /// Returns the wait state (dummy) clocks
+    pub const fn dummy_clocks(self) -> u8 {
+        self.dummy_clocks
+    }
+    /// Returns the `DUMMY` operand, which covers both the mode bit and wait state
+    /// clocks
+    pub const fn dummy_cycles(self) -> u8 {
+        self.mode_clocks + self.dummy_clocks
+    }
+}
+
+/// An erase type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EraseType {
+    size: u32,
+    opcode: u8,
+    four_byte_opcode: Option<u8>,
+}
+
+impl EraseType {
+    /// Returns the erase size, in bytes
+    pub const fn size(self) -> u32 {
+        self.size
+    }
+    /// Returns the opcode for 3 byte addresses
+    pub const fn opcode(self) -> u8 {
+        self.opcode
+    }
+    /// Returns the opcode for 4 byte addresses, if the device has one
+    pub const fn four_byte_opcode(self) -> Option<u8> {
+        self.four_byte_opcode
+    }
+}
+
+/// The xSPI Profile 1.0 parameters for 8D-8D-8D (octal DTR) operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XspiProfile {
+    read_opcode: u8,
+    status_address_bytes: u8,
+    status_dummy_cycles: u8,
+    /// Dummy cycles for each of the `XSPI_FREQUENCIES_MHZ`, or zero if not reported
+    dummy_cycles: [u8; XSPI_FREQUENCIES_MHZ.len()],
+}
+
+impl XspiProfile {
+    /// Returns the 8D-8D-8D read opcode
+    ///
+    /// The command extension is usually the inverted opcode.
+    pub const fn read_opcode(self) -> u8 {
+        self.read_opcode
+    }
+    /// Returns the address bytes of the 8D-8D-8D read status register command,
+    /// either 0 or 4
+    pub const fn status_address_bytes(self) -> u8 {
+        self.status_address_bytes
+    }
+    /// Returns the dummy cycles of the 8D-8D-8D read status register command,
+    /// either 4 or 8
+    pub const fn status_dummy_cycles(self) -> u8 {
+        self.status_dummy_cycles
+    }
+    /// Returns the read dummy cycles for a serial clock of `frequency_mhz`
+    ///
+    /// The profile reports dummy cycles at 100, 133, 166 and 200MHz. This uses the
+    /// lowest of those frequencies that covers `frequency_mhz`, and that has dummy
+    /// cycles. Odd dummy cycles round up to the next even number, since a DDR
+    /// transfer takes two bytes per cycle.
+    ///
+    /// Returns `None` if no reported frequency covers `frequency_mhz`.
+    pub const fn dummy_cycles(self, frequency_mhz: u16) -> Option<u8> {
+        let mut idx = 0;
+        while idx < XSPI_FREQUENCIES_MHZ.len() {
+            let cycles = self.dummy_cycles[idx];
+            if XSPI_FREQUENCIES_MHZ[idx] >= frequency_mhz && cycles != 0 {
+                return Some(cycles + (cycles & 1));
+            }
+            idx += 1;
+        }
+        None
+    }
+}
+
+/// The address bytes that the device supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressBytes {
+    /// Only 3 byte addresses
+    Three,
+    /// 3 byte addresses by default, and 4 byte addresses
+    ThreeOrFour,
+    /// Only 4 byte addresses
+    Four,
+}
+
+/// A sector map region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectorRegion {
+    size: u32,
+    erase_types: u8,
+}
+
+impl SectorRegion {
+    /// Returns the region size, in bytes
+    pub const fn size(self) -> u32 {
+        self.size
+    }
+    /// Returns `true` if the region supports the erase type
+    ///
+    /// `erase_type` is an index into [`Sfdp::erase_types`].
+    pub const fn supports(self, erase_type: usize) -> bool {
+        erase_type < ERASE_TYPES && self.erase_types & (1 << erase_type) != 0
+    }
+}
+
+/// The regions of a sector map configuration
+///
+/// Use [`Sfdp::sector_regions`] to create this iterator.
+#[derive(Debug, Clone)]
+pub struct SectorRegions<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    remaining: usize,
+}
+
+impl Iterator for SectorRegions<'_> {
+    type Item = SectorRegion;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        // find_sector_map checked the region sizes.
+        let region = sector_region(read_u32(self.bytes, self.offset))?;
+        self.offset += 4;
+        self.remaining -= 1;
+        Some(region)
+    }
+}
+
+/// Decode a region DWORD, or return `None` if the region is 4GiB or larger
+const fn sector_region(dword: u32) -> Option<SectorRegion> {
+    match ((dword >> 8) + 1).checked_mul(256) {
+        Some(size) => Some(SectorRegion {
+            size,
+            erase_types: (dword & 0xF) as u8,
+        }),
+        None => None,
+    }
+}
+
+/// A parsed SFDP dump
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone, Copy)]
+pub struct Sfdp<'a> {
+    bytes: &'a [u8],
+    basic: ParameterTable,
+    four_byte_address: Option<ParameterTable>,
+    sector_map: Option<ParameterTable>,
+    xspi_profile_table: Option<ParameterTable>,
+    xspi_profile: Option<XspiProfile>,
+    size: u32,
+    page_size: u32,
+    address_bytes: AddressBytes,
+    quad_enable: QuadEnable,
+    erase_types: [Option<EraseType>; ERASE_TYPES],
+}
+
+impl<'a> Sfdp<'a> {
+    /// Parse a SFDP dump
+    ///
+    /// `bytes` starts at SFDP address zero, and includes all parameter tables.
+    pub const fn parse(bytes: &'a [u8]) -> Result<Self, SfdpError> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(SfdpError::Truncated);
+        }
+        let signature = read_u32(bytes, 0);
+        if signature != SIGNATURE {
+            return Err(SfdpError::Signature(signature));
+        }
+        let parameter_headers = bytes[6] as usize + 1;
+
+        let mut basic = None;
+        let mut four_byte_address = None;
+        let mut sector_map = None;
+        let mut xspi_profile_table = None;
+        let mut idx = 0;
+        while idx < parameter_headers {
+            let table = match parameter_table(bytes, HEADER_SIZE + idx * PARAMETER_HEADER_SIZE) {
+                Ok(table) => table,
+                Err(err) => return Err(err),
+            };
+            // Keep the first table of each kind. For the basic table, that's the
+            // mandatory JESD216 table.
+            match table.id {
+                BASIC_TABLE_ID if basic.is_none() => basic = Some(table),
+                FOUR_BYTE_ADDRESS_TABLE_ID if four_byte_address.is_none() => {
+                    four_byte_address = Some(table)
+                }
+                SECTOR_MAP_TABLE_ID if sector_map.is_none() => sector_map = Some(table),
+                XSPI_PROFILE_TABLE_ID if xspi_profile_table.is_none() => {
+                    xspi_profile_table = Some(table)
+                }
+                _ => {}
+            }
+            idx += 1;
+        }
+        let basic = match basic {
+            Some(basic) if basic.dwords >= 9 => basic,
+            _ => return Err(SfdpError::MissingBasicTable),
+        };
+
+        let density = dword(bytes, basic, 2);
+        let size = if density & (1 << 31) == 0 {
+            // Density in bits, minus one.
+            (density + 1) / 8
+        } else {
+            // Density is 2^N bits.
+            let exponent = density & !(1 << 31);
+            if exponent < 3 || exponent > 34 {
+                return Err(SfdpError::Density(density));
+            }
+            1 << (exponent - 3)
+        };
+
+        let page_exponent = if basic.dwords >= 11 {
+            (dword(bytes, basic, 11) >> 4) & 0xF
+        } else {
+            0
+        };
+        let page_size = if page_exponent == 0 {
+            DEFAULT_PAGE_SIZE
+        } else {
+            1 << page_exponent
+        };
+
+        let address_bytes = match (dword(bytes, basic, 1) >> 17) & 0b11 {
+            0b00 => AddressBytes::Three,
+            0b01 => AddressBytes::ThreeOrFour,
+            _ => AddressBytes::Four,
+        };
+
+        let quad_enable = if basic.dwords >= 15 {
+            let requirement = ((dword(bytes, basic, 15) >> 20) & 0b111) as u8;
+            match requirement {
+                0b000 => QuadEnable::None,
+                0b010 => QuadEnable::Sr1Bit6,
+                0b011 => QuadEnable::Sr2Bit7,
+                0b001 | 0b100 | 0b101 => QuadEnable::Sr2Bit1,
+                0b110 => QuadEnable::Sr2Bit1WriteSr2,
+                _ => return Err(SfdpError::QuadEnable(requirement)),
+            }
+        } else {
+            QuadEnable::None
+        };
+
+        let mut erase_types = [None; ERASE_TYPES];
+        let mut idx = 0;
+        while idx < ERASE_TYPES {
+            let field = dword(bytes, basic, 8 + idx / 2) >> (16 * (idx % 2));
+            let exponent = field & 0xFF;
+            if exponent != 0 && exponent < 32 {
+                let four_byte_opcode = match four_byte_address {
+                    Some(table) => {
+                        let instructions = match table_dword(bytes, table, 1) {
+                            Ok(instructions) => instructions,
+                            Err(err) => return Err(err),
+                        };
+                        if instructions & (1 << (9 + idx)) != 0 {
+                            match table_dword(bytes, table, 2) {
+                                Ok(opcodes) => Some((opcodes >> (8 * idx)) as u8),
+                                Err(err) => return Err(err),
+                            }
+                        } else {
+                            None
+                        }
+                    }
+                    None => None,
+                };
+                erase_types[idx] = Some(EraseType {
+                    size: 1 << exponent,
+                    opcode: (field >> 8) as u8,
+                    four_byte_opcode,
+                });
+            }
+            idx += 1;
+        }
+
+        let xspi_profile = match xspi_profile_table {
+            Some(table) => match xspi_profile(bytes, table) {
+                Ok(profile) => Some(profile),
+                Err(err) => return Err(err),
+            },
+            None => None,
+        };
+
+        let sfdp = Sfdp {
+            bytes,
+            basic,
+            four_byte_address,
+            sector_map,
+            xspi_profile_table,
+            xspi_profile,
+            size,
+            page_size,
+            address_bytes,
+            quad_enable,
+            erase_types,
+        };
+        // Surface errors in the derived protocol and the sector map now, so that the
+        // other methods don't fail.
+        if let Err(err) = sfdp.try_protocol() {
+            return Err(err);
+        }
+        Ok(sfdp)
+    }
+
+    /// Returns the Basic Flash Parameter Table
+    pub const fn basic_table(&self) -> ParameterTable {
+        self.basic
+    }
+    /// Returns the 4-byte Address Instruction Table
+    pub const fn four_byte_address_table(&self) -> Option<ParameterTable> {
+        self.four_byte_address
+    }
+    /// Returns the Sector Map Parameter Table
+    pub const fn sector_map_table(&self) -> Option<ParameterTable> {
+        self.sector_map
+    }
+    /// Returns the xSPI Profile 1.0 table
+    pub const fn xspi_profile_table(&self) -> Option<ParameterTable> {
+        self.xspi_profile_table
+    }
+    /// Returns the xSPI Profile 1.0 parameters, or `None` if the device doesn't
+    /// have the table
+    pub const fn xspi_profile(&self) -> Option<XspiProfile> {
+        self.xspi_profile
+    }
+    /// Returns DWORD `n` of the table, counting from one like JESD216
+    ///
+    /// Returns `None` if the table is shorter than `n` DWORDs.
+    pub const fn dword(&self, table: ParameterTable, n: usize) -> Option<u32> {
+        if n == 0 || n > table.dwords {
+            None
+        } else {
+            Some(dword(self.bytes, table, n))
+        }
+    }
+
+    /// Returns the device size, in bytes
+    pub const fn size(&self) -> u32 {
+        self.size
+    }
+    /// Returns the page size, in bytes
+    ///
+    /// If the device doesn't report a page size, this is 256 bytes.
+    pub const fn page_size(&self) -> u32 {
+        self.page_size
+    }
+    /// Returns the supported address bytes
+    pub const fn address_bytes(&self) -> AddressBytes {
+        self.address_bytes
+    }
+    /// Returns the quad enable requirement
+    ///
+    /// If the device doesn't report a requirement, this is [`QuadEnable::None`].
+    pub const fn quad_enable(&self) -> QuadEnable {
+        self.quad_enable
+    }
+    /// Returns the erase types
+    pub const fn erase_types(&self) -> [Option<EraseType>; ERASE_TYPES] {
+        self.erase_types
+    }
+    /// Returns `true` if the device supports DTR reads
+    pub const fn supports_dtr(&self) -> bool {
+        dword(self.bytes, self.basic, 1) & (1 << 19) != 0
+    }
+    /// Returns the fast read for the I/O mode, or `None` if the device doesn't support
+    /// it
+    ///
+    /// All devices support the 1-1-1 fast read, `0x0B` with eight dummy clocks.
+    pub const fn fast_read(&self, io_mode: IoMode) -> Option<FastRead> {
+        let first = dword(self.bytes, self.basic, 1);
+        let (supported, field) = match io_mode {
+            IoMode::Single => {
+                return Some(FastRead {
+                    io_mode,
+                    opcode: 0x0B,
+                    mode_clocks: 0,
+                    dummy_clocks: 8,
+                })
+            }
+            IoMode::DualOutput => (first & (1 << 16), dword(self.bytes, self.basic, 4)),
+            IoMode::DualIo => (first & (1 << 20), dword(self.bytes, self.basic, 4) >> 16),
+            IoMode::QuadOutput => (first & (1 << 22), dword(self.bytes, self.basic, 3) >> 16),
+            IoMode::QuadIo => (first & (1 << 21), dword(self.bytes, self.basic, 3)),
+            IoMode::Qpi => (
+                dword(self.bytes, self.basic, 5) & (1 << 4),
+                dword(self.bytes, self.basic, 7) >> 16,
+            ),
+            IoMode::Opi => return None,
+        };
+        if supported == 0 {
+            return None;
+        }
+        Some(FastRead {
+            io_mode,
+            opcode: (field >> 8) as u8,
+            mode_clocks: ((field >> 5) & 0b111) as u8,
+            dummy_clocks: (field & 0b1_1111) as u8,
+        })
+    }
+    /// Returns the fastest 1-x-x read
+    ///
+    /// The read is the first supported read of 1-4-4, 1-1-4, 1-2-2, 1-1-2 and 1-1-1.
+    /// QPI reads are not considered, since a configuration block can't switch a device
+    /// into QPI mode.
+    pub const fn fastest_read(&self) -> FastRead {
+        const PREFERENCE: [IoMode; 4] = [
+            IoMode::QuadIo,
+            IoMode::QuadOutput,
+            IoMode::DualIo,
+            IoMode::DualOutput,
+        ];
+        let mut idx = 0;
+        while idx < PREFERENCE.len() {
+            if let Some(read) = self.fast_read(PREFERENCE[idx]) {
+                return read;
+            }
+            idx += 1;
+        }
+        match self.fast_read(IoMode::Single) {
+            Some(read) => read,
+            None => unreachable!(),
+        }
+    }
+    /// Returns the regions of a sector map configuration
+    ///
+    /// Returns `None` if there's no sector map, or if there's no map for the
+    /// configuration. If the sector map doesn't have configuration detection
+    /// commands, its only configuration is zero.
+    pub fn sector_regions(&self, configuration_id: u8) -> Option<SectorRegions<'a>> {
+        match find_sector_map(self.bytes, self.sector_map, Some(configuration_id)) {
+            Ok(Some((offset, regions))) => Some(SectorRegions {
+                bytes: self.bytes,
+                offset,
+                remaining: regions,
+            }),
+            _ => None,
+        }
+    }
+    /// Returns the index of the sector erase type
+    ///
+    /// This is the smallest erase type. If there's a sector map, this is the smallest
+    /// erase type that all regions of the first map support.
+    pub const fn sector_erase_type(&self) -> usize {
+        match self.try_erase_types() {
+            Ok((sector, _)) => sector,
+            Err(_) => unreachable!(),
+        }
+    }
+    /// Returns the protocol that uses the [fastest read](Sfdp::fastest_read)
+    ///
+    /// The sector erase is the [sector erase type](Sfdp::sector_erase_type), and the
+    /// block erase is the largest erase type. Devices larger than 16MiB use 4 byte
+    /// addresses.
+    pub const fn protocol(&self) -> Protocol {
+        match self.try_protocol() {
+            Ok(protocol) => protocol,
+            Err(_) => unreachable!(),
+        }
+    }
+    /// Returns the lookup table for the [protocol](Sfdp::protocol)
+    pub const fn lookup_table(&self) -> LookupTable {
+        self.protocol().lookup_table()
+    }
+    /// Create a serial NOR configuration block for the [protocol](Sfdp::protocol)
+    ///
+    /// The configuration block sets the quad enable bit, if the read needs it. The
+    /// FlexSPI serial clock keeps its 30MHz default.
+    pub const fn configuration_block(&self) -> nor::ConfigurationBlock {
+        let (sector, _) = match self.try_erase_types() {
+            Ok(erase_types) => erase_types,
+            Err(_) => unreachable!(),
+        };
+        let sector_size = match self.erase_types[sector] {
+            Some(erase_type) => erase_type.size,
+            None => unreachable!(),
+        };
+        let mem_cfg = spi_configuration_block(&self.protocol(), self.quad_enable, self.size);
+        nor::ConfigurationBlock::new(mem_cfg)
+            .page_size(self.page_size)
+            .sector_size(sector_size)
+            .ip_cmd_serial_clk_freq(nor::SerialClockFrequency::MHz30)
+    }
+
+    /// Returns the sector erase type and the block erase type
+    const fn try_erase_types(&self) -> Result<(usize, usize), SfdpError> {
+        let map = match find_sector_map(self.bytes, self.sector_map, None) {
+            Ok(map) => map,
+            Err(err) => return Err(err),
+        };
+        let mut sector: Option<usize> = None;
+        let mut block: Option<usize> = None;
+        let mut idx = 0;
+        while idx < ERASE_TYPES {
+            if let Some(erase_type) = self.erase_types[idx] {
+                let in_all_regions = match map {
+                    Some((offset, regions)) => {
+                        let mut region = 0;
+                        let mut supported = true;
+                        while region < regions {
+                            let dword = read_u32(self.bytes, offset + 4 * region);
+                            supported &= match sector_region(dword) {
+                                Some(region) => region.supports(idx),
+                                None => unreachable!(),
+                            };
+                            region += 1;
+                        }
+                        supported
+                    }
+                    None => true,
+                };
+                sector = match sector {
+                    Some(current) if !in_all_regions => Some(current),
+                    Some(current) => match self.erase_types[current] {
+                        Some(smallest) if smallest.size <= erase_type.size => Some(current),
+                        _ => Some(idx),
+                    },
+                    None if in_all_regions => Some(idx),
+                    None => None,
+                };
+                block = match block {
+                    Some(current) => match self.erase_types[current] {
+                        Some(largest) if largest.size >= erase_type.size => Some(current),
+                        _ => Some(idx),
+                    },
+                    None => Some(idx),
+                };
+            }
+            idx += 1;
+        }
+        match (sector, block) {
+            (Some(sector), Some(block)) => Ok((sector, block)),
+            _ => Err(SfdpError::EraseType),
+        }
+    }
+
+    const fn try_protocol(&self) -> Result<Protocol, SfdpError> {
+        let (sector, block) = match self.try_erase_types() {
+            Ok(erase_types) => erase_types,
+            Err(err) => return Err(err),
+        };
+        let read = self.fastest_read();
+        let four_byte = self.size > THREE_BYTE_ADDRESS_LIMIT
+            || matches!(self.address_bytes, AddressBytes::Four);
+        let address_bits = if four_byte {
+            AddressBits::B32
+        } else {
+            AddressBits::B24
+        };
+        let (sector_erase, block_erase) = match (self.erase_types[sector], self.erase_types[block])
+        {
+            (Some(sector), Some(block)) => (sector, block),
+            _ => return Err(SfdpError::EraseType),
+        };
+
+        let protocol =
+            Protocol::new(read.io_mode, address_bits).read_dummy_cycles(read.dummy_cycles());
+        // Devices that only use 4 byte addresses use the 3 byte address opcodes.
+        if !four_byte || matches!(self.address_bytes, AddressBytes::Four) {
+            return Ok(protocol
+                .read_opcode(read.opcode)
+                .sector_erase_opcode(sector_erase.opcode)
+                .block_erase_opcode(block_erase.opcode)
+                .page_program_opcode(0x02));
+        }
+
+        // Otherwise, use the 4 byte address opcodes.
+        let instructions = match self.four_byte_address {
+            Some(table) => match table_dword(self.bytes, table, 1) {
+                Ok(instructions) => instructions,
+                Err(err) => return Err(err),
+            },
+            None => return Err(SfdpError::FourByteAddress),
+        };
+        let (read_bit, read_opcode) = match read.io_mode {
+            IoMode::Single => (1, 0x0C),
+            IoMode::DualOutput => (2, 0x3C),
+            IoMode::DualIo => (3, 0xBC),
+            IoMode::QuadOutput => (4, 0x6C),
+            IoMode::QuadIo => (5, 0xEC),
+            IoMode::Qpi | IoMode::Opi => return Err(SfdpError::FourByteAddress),
+        };
+        const PAGE_PROGRAM_BIT: u32 = 6;
+        if instructions & (1 << read_bit) == 0 || instructions & (1 << PAGE_PROGRAM_BIT) == 0 {
+            return Err(SfdpError::FourByteAddress);
+        }
+        match (sector_erase.four_byte_opcode, block_erase.four_byte_opcode) {
+            (Some(sector_erase), Some(block_erase)) => Ok(protocol
+                .read_opcode(read_opcode)
+                .sector_erase_opcode(sector_erase)
+                .block_erase_opcode(block_erase)
+                .page_program_opcode(0x12)),
+            _ => Err(SfdpError::FourByteAddress),
+        }
+    }
+}
+
+/// Returns DWORD `n` of the table, counting from one
+///
+/// The caller checks that the table has `n` DWORDs.
+const fn dword(bytes: &[u8], table: ParameterTable, n: usize) -> u32 {
+    read_u32(bytes, table.offset + 4 * (n - 1))
+}
+
+/// Returns DWORD `n` of an optional table, or an error if the table is shorter
+/// than `n` DWORDs
+const fn table_dword(bytes: &[u8], table: ParameterTable, n: usize) -> Result<u32, SfdpError> {
+    if n > table.dwords {
+        Err(SfdpError::ShortTable(table.id))
+    } else {
+        Ok(dword(bytes, table, n))
+    }
+}
+
+/// Decode the xSPI Profile 1.0 table
+const fn xspi_profile(bytes: &[u8], table: ParameterTable) -> Result<XspiProfile, SfdpError> {
+    let (first, fourth, fifth) = match (
+        table_dword(bytes, table, 1),
+        table_dword(bytes, table, 4),
+        table_dword(bytes, table, 5),
+    ) {
+        (Ok(first), Ok(fourth), Ok(fifth)) => (first, fourth, fifth),
+        _ => return Err(SfdpError::ShortTable(table.id)),
+    };
+    Ok(XspiProfile {
+        read_opcode: (first >> 8) as u8,
+        status_address_bytes: if first & (1 << 29) != 0 { 4 } else { 0 },
+        status_dummy_cycles: if first & (1 << 28) != 0 { 8 } else { 4 },
+        dummy_cycles: [
+            dummy_cycles_field(fifth, 7),
+            dummy_cycles_field(fifth, 17),
+            dummy_cycles_field(fifth, 27),
+            dummy_cycles_field(fourth, 7),
+        ],
+    })
+}
+
+/// Returns the five bit dummy cycles field at `shift`
+const fn dummy_cycles_field(dword: u32, shift: u32) -> u8 {
+    ((dword >> shift) & 0b1_1111) as u8
+}
+
+/// Read the parameter header at `offset`, and check that its table is in `bytes`
+const fn parameter_table(bytes: &[u8], offset: usize) -> Result<ParameterTable, SfdpError> {
+    if offset + PARAMETER_HEADER_SIZE > bytes.len() {
+        return Err(SfdpError::Truncated);
+    }
+    let table = ParameterTable {
+        id: u16::from_le_bytes([bytes[offset], bytes[offset + 7]]),
+        minor: bytes[offset + 1],
+        major: bytes[offset + 2],
+        dwords: bytes[offset + 3] as usize,
+        offset: u32::from_le_bytes([bytes[offset + 4], bytes[offset + 5], bytes[offset + 6], 0])
+            as usize,
+    };
+    if table.offset + 4 * table.dwords > bytes.len() {
+        return Err(SfdpError::Truncated);
+    }
+    Ok(table)
+}
+
+/// Find a map descriptor in the sector map
+///
+/// If `configuration_id` is `None`, returns the first map. Returns the offset of the
+/// map's first region, and the number of regions.
+const fn find_sector_map(
+    bytes: &[u8],
+    table: Option<ParameterTable>,
+    configuration_id: Option<u8>,
+) -> Result<Option<(usize, usize)>, SfdpError> {
+    let table = match table {
+        Some(table) => table,
+        None => return Ok(None),
+    };
+    let end = table.offset + 4 * table.dwords;
+    let mut offset = table.offset;
+    while offset + 4 <= end {
+        let descriptor = read_u32(bytes, offset);
+        let last = descriptor & 0b01 != 0;
+        let is_map = descriptor & 0b10 != 0;
+        if is_map {
+            let id = (descriptor >> 8) as u8;
+            let regions = ((descriptor >> 16) & 0xFF) as usize + 1;
+            if offset + 4 * (1 + regions) > end {
+                return Err(SfdpError::SectorMap);
+            }
+            let mut region = 0;
+            while region < regions {
+                if sector_region(read_u32(bytes, offset + 4 * (1 + region))).is_none() {
+                    return Err(SfdpError::SectorMap);
+                }
+                region += 1;
+            }
+            let matches = match configuration_id {
+                Some(configuration_id) => configuration_id == id,
+                None => true,
+            };
+            if matches {
+                return Ok(Some((offset + 4, regions)));
+            }
+            offset += 4 * (1 + regions);
+        } else {
+            // A configuration detection command, followed by its address.
+            offset += 8;
+        }
+        if last {
+            return Ok(None);
+        }
+    }
+    Err(SfdpError::SectorMap)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Sfdp, SfdpError};
+
+    #[test]
+    fn errors() {
+        assert_eq!(Sfdp::parse(&[0; 4]).unwrap_err(), SfdpError::Truncated);
+        assert_eq!(Sfdp::parse(&[0; 16]).unwrap_err(), SfdpError::Signature(0));
+        // Signature, revision 1.0, one parameter header, which is truncated.
+        const NO_HEADER: [u8; 8] = [b'S', b'F', b'D', b'P', 0x00, 0x01, 0x00, 0xFF];
+        assert_eq!(Sfdp::parse(&NO_HEADER).unwrap_err(), SfdpError::Truncated);
+        assert_eq!(
+            SfdpError::Density(0xFFFF_FFFF).to_string(),
+            "The SFDP density is 4GiB or larger (0xFFFFFFFF)"
+        );
+    }
+}
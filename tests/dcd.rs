@@ -0,0 +1,31 @@
+//! Device configuration data, excerpted from the i.MX RT1050 EVKB DCD
+
+use imxrt_boot_gen::dcd::{self, Condition, Width};
+
+const CCM_CCGR0: u32 = 0x400F_C068;
+const CCM_CCGR1: u32 = 0x400F_C06C;
+const CCM_CCGR2: u32 = 0x400F_C070;
+const SEMC_INTR: u32 = 0x402F_003C;
+
+const DCD_BUILDER: dcd::Builder = dcd::Builder::new()
+    .write(Width::Word, CCM_CCGR0, 0xFFFF_FFFF)
+    .write(Width::Word, CCM_CCGR1, 0xFFFF_FFFF)
+    .write(Width::Word, CCM_CCGR2, 0xFFFF_FFFF)
+    .check(Width::Word, Condition::AnySet, SEMC_INTR, 0x0000_0001);
+
+static DCD: [u8; DCD_BUILDER.len()] = DCD_BUILDER.build();
+
+#[test]
+fn evkb_excerpt() {
+    assert_eq!(&DCD, &EXPECTED);
+}
+
+const EXPECTED: [u8; 44] = [
+    0xD2, 0x00, 0x2C, 0x41, // DCD header
+    0xCC, 0x00, 0x1C, 0x04, // write data, 4 bytes
+    0x40, 0x0F, 0xC0, 0x68, 0xFF, 0xFF, 0xFF, 0xFF, // CCM_CCGR0
+    0x40, 0x0F, 0xC0, 0x6C, 0xFF, 0xFF, 0xFF, 0xFF, // CCM_CCGR1
+    0x40, 0x0F, 0xC0, 0x70, 0xFF, 0xFF, 0xFF, 0xFF, // CCM_CCGR2
+    0xCF, 0x00, 0x0C, 0x1C, // check data, any bit set, 4 bytes
+    0x40, 0x2F, 0x00, 0x3C, 0x00, 0x00, 0x00, 0x01, // SEMC_INTR
+];
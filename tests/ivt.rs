@@ -0,0 +1,65 @@
+//! Image vector table and boot data from the i.MX RT1060 EVK
+
+use imxrt_boot_gen::ivt::*;
+
+const FLASH_BASE: u32 = 0x6000_0000;
+const FLASH_SIZE: u32 = 0x0080_0000;
+const IVT_ADDRESS: u32 = FLASH_BASE + 0x1000;
+const BOOT_DATA_ADDRESS: u32 = IVT_ADDRESS + 0x20;
+const DCD_ADDRESS: u32 = BOOT_DATA_ADDRESS + 0x10;
+const IMAGE_ENTRY_ADDRESS: u32 = FLASH_BASE + 0x2000;
+
+const IMAGE_VECTOR_TABLE: ImageVectorTable = ImageVectorTable::new(IMAGE_ENTRY_ADDRESS)
+    .dcd(DCD_ADDRESS)
+    .boot_data(BOOT_DATA_ADDRESS)
+    .self_address(IVT_ADDRESS);
+
+const BOOT_DATA: BootData = BootData::new().start(FLASH_BASE).length(FLASH_SIZE);
+
+fn to_words(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks_exact(4)
+        .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+        .collect()
+}
+
+#[test]
+fn image_vector_table() {
+    let actual: &[u32; 8] = unsafe { core::mem::transmute(&IMAGE_VECTOR_TABLE) };
+    assert_eq!(actual, &EXPECTED_IVT);
+}
+
+#[test]
+fn image_vector_table_to_bytes() {
+    assert_eq!(to_words(&IMAGE_VECTOR_TABLE.to_bytes()), EXPECTED_IVT);
+}
+
+#[test]
+fn boot_data() {
+    let actual: &[u32; 4] = unsafe { core::mem::transmute(&BOOT_DATA) };
+    assert_eq!(actual, &EXPECTED_BOOT_DATA);
+}
+
+#[test]
+fn boot_data_to_bytes() {
+    assert_eq!(to_words(&BOOT_DATA.to_bytes()), EXPECTED_BOOT_DATA);
+}
+
+// The IVT from the EVK's XIP boot header, fsl_flexspi_nor_boot.c
+const EXPECTED_IVT: [u32; 8] = [
+    0x4020_00D1, // header: version, length (big endian), tag
+    0x6000_2000, // entry
+    0,           // reserved
+    0x6000_1030, // dcd
+    0x6000_1020, // boot data
+    0x6000_1000, // self
+    0,           // csf
+    0,           // reserved
+];
+
+const EXPECTED_BOOT_DATA: [u32; 4] = [
+    0x6000_0000, // start
+    0x0080_0000, // size
+    0,           // plugin
+    0,           // reserved
+];
@@ -0,0 +1,153 @@
+//! JEDEC serial NOR lookup tables, compared with known-good lookup tables
+//!
+//! Each expected lookup table is written in NXP's `FLEXSPI_LUT_SEQ` word format, with
+//! two instructions per word.
+
+use imxrt_boot_gen::flexspi::{AddressBits, Command, LookupTable, SerialClockFrequency};
+use imxrt_boot_gen::serial_flash::devices::{dummy_operand, DummyCycles, S26KS512S};
+use imxrt_boot_gen::serial_flash::jedec::{DataRate, IoMode, Protocol};
+use imxrt_boot_gen::serial_flash::nor;
+
+const LUT_WORDS: usize = 64;
+
+fn words(lookup_table: &LookupTable) -> Vec<u32> {
+    lookup_table
+        .to_bytes()
+        .chunks_exact(4)
+        .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+        .collect()
+}
+
+/// Lookup table from the i.MX RT1060 EVKB QSPI FCB
+///
+/// ISSI IS25WP064A, quad I/O read (1-4-4) with 3 byte addresses.
+#[test]
+fn imxrt1060_evkb_qspi() {
+    const LUT: LookupTable = Protocol::new(IoMode::QuadIo, AddressBits::B24)
+        .read_dummy_cycles(6)
+        .lookup_table();
+
+    let mut expected = [0u32; LUT_WORDS];
+    // Read
+    expected[0] = 0x0A18_04EB;
+    expected[1] = 0x2604_3206;
+    // Read status
+    expected[4] = 0x2404_0405;
+    // Write enable
+    expected[4 * 3] = 0x0000_0406;
+    // Erase sector
+    expected[4 * 5] = 0x0818_0420;
+    // Erase block
+    expected[4 * 8] = 0x0818_04D8;
+    // Page program
+    expected[4 * 9] = 0x0818_0402;
+    expected[4 * 9 + 1] = 0x0000_2004;
+    // Chip erase
+    expected[4 * 11] = 0x0000_0460;
+
+    assert_eq!(words(&LUT), expected);
+}
+
+/// Quad I/O DTR (1-4-4 DTR) lookup table
+///
+/// The IS25WP064A from the i.MX RT1060 EVKB, using its DTR fast read quad I/O
+/// (`0xED`) with 6 dummy cycles. Only the read uses DTR.
+#[test]
+fn quad_io_dtr() {
+    const LUT: LookupTable = Protocol::new(IoMode::QuadIo, AddressBits::B24)
+        .data_rate(DataRate::Dtr)
+        .read_dummy_cycles(6)
+        .lookup_table();
+
+    let mut expected = [0u32; LUT_WORDS];
+    // Read
+    expected[0] = 0x8A18_04ED;
+    expected[1] = 0xA604_B206;
+    // Read status
+    expected[4] = 0x2404_0405;
+    // Write enable
+    expected[4 * 3] = 0x0000_0406;
+    // Erase sector
+    expected[4 * 5] = 0x0818_0420;
+    // Erase block
+    expected[4 * 8] = 0x0818_04D8;
+    // Page program
+    expected[4 * 9] = 0x0818_0402;
+    expected[4 * 9 + 1] = 0x0000_2004;
+    // Chip erase
+    expected[4 * 11] = 0x0000_0460;
+
+    assert_eq!(words(&LUT), expected);
+}
+
+/// Lookup table from the i.MX RT500 and RT600 EVK octal FCB
+///
+/// Macronix MX25UM51345G, octal DDR (8-8-8 DTR) read with 4 byte addresses and
+/// inverted command extensions. The SPI status and write enable sequences are in
+/// the `ReadStatus` and `WriteEnable` slots, and the OPI sequences are in the
+/// `ReadStatusXpi` and `WriteEnableXpi` slots. The EVK FCB also has sequences
+/// that configure the device's dummy cycles and switch it into OPI mode; the
+/// generator doesn't emit those, so they're not compared.
+///
+/// The device's default 8D-8D-8D read has 20 dummy cycles, and the FCB's
+/// `DUMMY_DDR` operand is `0x14`.
+#[test]
+fn imxrt500_evk_octal() {
+    const DUMMY_CYCLES: &[DummyCycles] = &[DummyCycles::new(IoMode::Opi, DataRate::Dtr, 20, 200)];
+    const LUT: LookupTable = Protocol::new(IoMode::Opi, AddressBits::B32)
+        .data_rate(DataRate::Dtr)
+        .read_dummy_cycles(dummy_operand(
+            DUMMY_CYCLES,
+            IoMode::Opi,
+            DataRate::Dtr,
+            SerialClockFrequency::MHz133,
+        ))
+        .lookup_table();
+
+    let mut expected = [0u32; LUT_WORDS];
+    // Read
+    expected[0] = 0x8711_87EE;
+    expected[1] = 0xB314_8B20;
+    expected[2] = 0x0000_A704;
+    // Read status (SPI)
+    expected[4] = 0x2404_0405;
+    // Read status (OPI)
+    expected[4 * 2] = 0x87FA_8705;
+    expected[4 * 2 + 1] = 0xB314_8B20;
+    expected[4 * 2 + 2] = 0x0000_A704;
+    // Write enable (SPI)
+    expected[4 * 3] = 0x0000_0406;
+    // Write enable (OPI)
+    expected[4 * 4] = 0x87F9_8706;
+    // Erase sector
+    expected[4 * 5] = 0x87DE_8721;
+    expected[4 * 5 + 1] = 0x0000_8B20;
+    // Erase block
+    expected[4 * 8] = 0x8723_87DC;
+    expected[4 * 8 + 1] = 0x0000_8B20;
+    // Page program
+    expected[4 * 9] = 0x87ED_8712;
+    expected[4 * 9 + 1] = 0xA304_8B20;
+    // Chip erase
+    expected[4 * 11] = 0x879F_8760;
+
+    assert_eq!(words(&LUT), expected);
+}
+
+/// Read sequence from the i.MX RT1050 EVKB HyperFlash FCB
+///
+/// Cypress S26KS512S, with the default 6 cycles of initial latency. The FCB's
+/// `DUMMY_DDR` operand is 6.
+#[test]
+fn imxrt1050_evkb_hyperflash() {
+    const NOR_CB: nor::ConfigurationBlock = S26KS512S.configuration_block(IoMode::Opi);
+    let lookup_table = NOR_CB.get_mem_cfg().get_lookup_table();
+
+    let mut expected = [0u32; LUT_WORDS];
+    expected[0] = 0x8B18_87A0;
+    expected[1] = 0xB306_8F10;
+    expected[2] = 0x0000_A704;
+
+    let read = LookupTable::new().command(Command::Read, *lookup_table.get(Command::Read));
+    assert_eq!(words(&read), expected);
+}
@@ -0,0 +1,309 @@
+//! SFDP parsing, and the lookup tables and configuration blocks derived from SFDP
+//!
+//! See `fixtures/README.md` for the provenance of each SFDP dump. The error tests
+//! assemble dumps from the fixtures' parameter tables.
+
+use imxrt_boot_gen::flexspi::AddressBits;
+use imxrt_boot_gen::serial_flash::devices::{Device, DummyCycles, JedecId, QuadEnable};
+use imxrt_boot_gen::serial_flash::jedec::{DataRate, IoMode, Protocol};
+use imxrt_boot_gen::serial_flash::sfdp::{AddressBytes, Sfdp, SfdpError};
+
+const W25Q64JV: &[u8] = include_bytes!("fixtures/w25q64jv_datasheet.bin");
+const QUAD_32MIB: &[u8] = include_bytes!("fixtures/synthetic_quad_32mib.bin");
+const OCTAL_64MIB: &[u8] = include_bytes!("fixtures/synthetic_octal_64mib.bin");
+
+const BASIC: u16 = 0xFF00;
+const SECTOR_MAP: u16 = 0xFF81;
+const FOUR_BYTE_ADDRESS: u16 = 0xFF84;
+const XSPI_PROFILE: u16 = 0xFF05;
+
+/// Assemble a SFDP dump, with the parameter headers followed by the tables
+fn dump(tables: &[(u16, &[u32])]) -> Vec<u8> {
+    let mut bytes = vec![b'S', b'F', b'D', b'P', 0x06, 0x01];
+    bytes.push(tables.len() as u8 - 1);
+    bytes.push(0xFF);
+    let mut pointer = 8 + 8 * tables.len();
+    for (id, dwords) in tables {
+        bytes.extend_from_slice(&[id.to_le_bytes()[0], 0x00, 0x01, dwords.len() as u8]);
+        bytes.extend_from_slice(&(pointer as u32).to_le_bytes()[..3]);
+        bytes.push(id.to_le_bytes()[1]);
+        pointer += 4 * dwords.len();
+    }
+    for (_, dwords) in tables {
+        for dword in *dwords {
+            bytes.extend_from_slice(&dword.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+/// Returns the DWORDs of the parameter table with the ID
+fn table(bytes: &[u8], id: u16) -> Vec<u32> {
+    let sfdp = Sfdp::parse(bytes).unwrap();
+    let table = [
+        Some(sfdp.basic_table()),
+        sfdp.four_byte_address_table(),
+        sfdp.sector_map_table(),
+        sfdp.xspi_profile_table(),
+    ]
+    .iter()
+    .flatten()
+    .find(|table| table.id() == id)
+    .copied()
+    .unwrap();
+    (1..=table.len())
+        .map(|n| sfdp.dword(table, n).unwrap())
+        .collect()
+}
+
+#[test]
+fn w25q64jv() {
+    let sfdp = Sfdp::parse(W25Q64JV).unwrap();
+
+    assert_eq!(sfdp.size(), 8 * 1024 * 1024);
+    assert_eq!(sfdp.page_size(), 256);
+    assert_eq!(sfdp.address_bytes(), AddressBytes::Three);
+    assert_eq!(sfdp.quad_enable(), QuadEnable::Sr2Bit1);
+    assert!(!sfdp.supports_dtr());
+    assert!(sfdp.four_byte_address_table().is_none());
+    assert!(sfdp.sector_map_table().is_none());
+    assert!(sfdp.xspi_profile().is_none());
+
+    let erase_types = sfdp.erase_types();
+    let sizes_and_opcodes: Vec<_> = erase_types
+        .iter()
+        .map(|erase_type| erase_type.map(|erase_type| (erase_type.size(), erase_type.opcode())))
+        .collect();
+    assert_eq!(
+        sizes_and_opcodes,
+        [
+            Some((4 * 1024, 0x20)),
+            Some((32 * 1024, 0x52)),
+            Some((64 * 1024, 0xD8)),
+            None
+        ]
+    );
+    assert_eq!(sfdp.sector_erase_type(), 0);
+
+    let read = sfdp.fastest_read();
+    assert_eq!(read.io_mode(), IoMode::QuadIo);
+    assert_eq!(read.opcode(), 0xEB);
+    assert_eq!(read.dummy_cycles(), 6);
+    let dual = sfdp.fast_read(IoMode::DualIo).unwrap();
+    assert_eq!((dual.opcode(), dual.dummy_cycles()), (0xBB, 4));
+    assert!(sfdp.fast_read(IoMode::Qpi).is_none());
+
+    assert_eq!(
+        sfdp.lookup_table(),
+        Protocol::new(IoMode::QuadIo, AddressBits::B24)
+            .read_dummy_cycles(6)
+            .lookup_table()
+    );
+
+    // The same device, described by hand.
+    const DEVICE: Device = Device::spi("W25Q64JV", JedecId::new(0xEF, 0x40, 0x17), 8 * 1024 * 1024)
+        .io_modes(&[IoMode::QuadIo])
+        .dummy_cycles(&[DummyCycles::new(IoMode::QuadIo, DataRate::Sdr, 6, 133)])
+        .quad_enable(QuadEnable::Sr2Bit1);
+    assert_eq!(
+        sfdp.configuration_block(),
+        DEVICE.configuration_block(IoMode::QuadIo)
+    );
+}
+
+#[test]
+fn four_byte_addresses_and_sector_map() {
+    let sfdp = Sfdp::parse(QUAD_32MIB).unwrap();
+
+    assert_eq!(sfdp.size(), 32 * 1024 * 1024);
+    assert_eq!(sfdp.address_bytes(), AddressBytes::ThreeOrFour);
+    assert_eq!(sfdp.quad_enable(), QuadEnable::None);
+
+    let xspi_profile = sfdp.xspi_profile_table().unwrap();
+    assert_eq!(xspi_profile.len(), 5);
+    assert_eq!(sfdp.dword(xspi_profile, 1), Some(0xFFFF_EEEE));
+    assert_eq!(sfdp.dword(xspi_profile, 6), None);
+    // The profile doesn't report dummy cycles.
+    assert_eq!(sfdp.xspi_profile().unwrap().dummy_cycles(100), None);
+
+    let regions: Vec<_> = sfdp
+        .sector_regions(0)
+        .unwrap()
+        .map(|region| (region.size(), region.supports(0), region.supports(2)))
+        .collect();
+    assert_eq!(
+        regions,
+        [
+            (64 * 1024, true, true),
+            (32 * 1024 * 1024 - 64 * 1024, false, true)
+        ]
+    );
+    assert!(sfdp.sector_regions(1).is_none());
+
+    let erase_types = sfdp.erase_types();
+    assert_eq!(erase_types[0].unwrap().four_byte_opcode(), Some(0x21));
+    assert_eq!(erase_types[2].unwrap().four_byte_opcode(), Some(0xDC));
+    // 4KiB erases don't cover the second region.
+    assert_eq!(sfdp.sector_erase_type(), 2);
+
+    let read = sfdp.fastest_read();
+    assert_eq!((read.opcode(), read.dummy_cycles()), (0xEB, 10));
+    let qpi = sfdp.fast_read(IoMode::Qpi).unwrap();
+    assert_eq!((qpi.opcode(), qpi.dummy_cycles()), (0xEB, 10));
+
+    assert_eq!(
+        sfdp.lookup_table(),
+        Protocol::new(IoMode::QuadIo, AddressBits::B32)
+            .read_opcode(0xEC)
+            .read_dummy_cycles(10)
+            .sector_erase_opcode(0xDC)
+            .block_erase_opcode(0xDC)
+            .page_program_opcode(0x12)
+            .lookup_table()
+    );
+    let nor_cb = sfdp.configuration_block();
+    assert_eq!(nor_cb.get_sector_size(), 64 * 1024);
+    assert_eq!(nor_cb.get_page_size(), 256);
+}
+
+#[test]
+fn octal_xspi_profile() {
+    let sfdp = Sfdp::parse(OCTAL_64MIB).unwrap();
+
+    assert_eq!(sfdp.size(), 64 * 1024 * 1024);
+    assert_eq!(sfdp.address_bytes(), AddressBytes::Four);
+    assert!(sfdp.supports_dtr());
+    assert!(sfdp.four_byte_address_table().is_none());
+
+    let profile = sfdp.xspi_profile().unwrap();
+    assert_eq!(profile.read_opcode(), 0xEE);
+    assert_eq!(profile.status_address_bytes(), 4);
+    assert_eq!(profile.status_dummy_cycles(), 8);
+    assert_eq!(profile.dummy_cycles(30), Some(10));
+    assert_eq!(profile.dummy_cycles(100), Some(10));
+    // 13 cycles at 133MHz round up to 14.
+    assert_eq!(profile.dummy_cycles(120), Some(14));
+    assert_eq!(profile.dummy_cycles(133), Some(14));
+    assert_eq!(profile.dummy_cycles(166), Some(16));
+    assert_eq!(profile.dummy_cycles(200), Some(20));
+    assert_eq!(profile.dummy_cycles(250), None);
+
+    // The configuration block reads in 1-1-1 mode, with 4 byte addresses.
+    let read = sfdp.fastest_read();
+    assert_eq!(read.io_mode(), IoMode::Single);
+    assert_eq!(
+        sfdp.lookup_table(),
+        Protocol::new(IoMode::Single, AddressBits::B32)
+            .read_opcode(0x0B)
+            .read_dummy_cycles(8)
+            .sector_erase_opcode(0x21)
+            .block_erase_opcode(0xDC)
+            .page_program_opcode(0x02)
+            .lookup_table()
+    );
+    let nor_cb = sfdp.configuration_block();
+    assert_eq!(nor_cb.check(), Ok(()));
+    assert_eq!(nor_cb.get_sector_size(), 4 * 1024);
+}
+
+#[test]
+fn errors() {
+    let w25q64jv_basic = table(W25Q64JV, BASIC);
+    let quad_basic = table(QUAD_32MIB, BASIC);
+    let quad_four_byte_address = table(QUAD_32MIB, FOUR_BYTE_ADDRESS);
+
+    let mut bytes = W25Q64JV.to_vec();
+    bytes[0] = b'X';
+    assert_eq!(
+        Sfdp::parse(&bytes).unwrap_err(),
+        SfdpError::Signature(0x5044_4658)
+    );
+
+    assert_eq!(
+        Sfdp::parse(&W25Q64JV[..W25Q64JV.len() - 1]).unwrap_err(),
+        SfdpError::Truncated
+    );
+
+    let bytes = dump(&[(FOUR_BYTE_ADDRESS, &quad_four_byte_address)]);
+    assert_eq!(
+        Sfdp::parse(&bytes).unwrap_err(),
+        SfdpError::MissingBasicTable
+    );
+
+    let mut basic = w25q64jv_basic.clone();
+    basic[14] = 0x0070_0000;
+    let bytes = dump(&[(BASIC, &basic)]);
+    assert_eq!(
+        Sfdp::parse(&bytes).unwrap_err(),
+        SfdpError::QuadEnable(0b111)
+    );
+
+    let mut basic = w25q64jv_basic.clone();
+    basic[1] = 0x8000_0023;
+    let bytes = dump(&[(BASIC, &basic)]);
+    assert_eq!(
+        Sfdp::parse(&bytes).unwrap_err(),
+        SfdpError::Density(0x8000_0023)
+    );
+
+    let mut basic = w25q64jv_basic;
+    basic[7] = 0;
+    basic[8] = 0;
+    let bytes = dump(&[(BASIC, &basic)]);
+    assert_eq!(Sfdp::parse(&bytes).unwrap_err(), SfdpError::EraseType);
+
+    // 32MiB, but no 4 byte address instructions.
+    let bytes = dump(&[(BASIC, &quad_basic)]);
+    assert_eq!(Sfdp::parse(&bytes).unwrap_err(), SfdpError::FourByteAddress);
+
+    // A map descriptor with two regions, but no region DWORDs.
+    let bytes = dump(&[
+        (BASIC, &quad_basic),
+        (FOUR_BYTE_ADDRESS, &quad_four_byte_address),
+        (SECTOR_MAP, &[0x0001_0003]),
+    ]);
+    assert_eq!(Sfdp::parse(&bytes).unwrap_err(), SfdpError::SectorMap);
+
+    // A 4GiB region.
+    let bytes = dump(&[
+        (BASIC, &quad_basic),
+        (FOUR_BYTE_ADDRESS, &quad_four_byte_address),
+        (SECTOR_MAP, &[0x0000_0003, 0xFFFF_FF01]),
+    ]);
+    assert_eq!(Sfdp::parse(&bytes).unwrap_err(), SfdpError::SectorMap);
+}
+
+#[test]
+fn short_tables() {
+    let quad_basic = table(QUAD_32MIB, BASIC);
+    let quad_four_byte_address = table(QUAD_32MIB, FOUR_BYTE_ADDRESS);
+
+    // The 4 byte address erase opcodes are in DWORD2, which is missing. The table
+    // is at the end of the dump.
+    let bytes = dump(&[
+        (BASIC, &quad_basic),
+        (FOUR_BYTE_ADDRESS, &quad_four_byte_address[..1]),
+    ]);
+    assert_eq!(
+        Sfdp::parse(&bytes).unwrap_err(),
+        SfdpError::ShortTable(FOUR_BYTE_ADDRESS)
+    );
+    let bytes = dump(&[(BASIC, &quad_basic), (FOUR_BYTE_ADDRESS, &[])]);
+    assert_eq!(
+        Sfdp::parse(&bytes).unwrap_err(),
+        SfdpError::ShortTable(FOUR_BYTE_ADDRESS)
+    );
+
+    // The dummy cycles are in DWORD4 and DWORD5.
+    let octal_basic = table(OCTAL_64MIB, BASIC);
+    let xspi_profile = table(OCTAL_64MIB, XSPI_PROFILE);
+    let bytes = dump(&[(BASIC, &octal_basic), (XSPI_PROFILE, &xspi_profile[..4])]);
+    assert_eq!(
+        Sfdp::parse(&bytes).unwrap_err(),
+        SfdpError::ShortTable(XSPI_PROFILE)
+    );
+    assert_eq!(
+        SfdpError::ShortTable(XSPI_PROFILE).to_string(),
+        "A SFDP parameter table is too short (0xFF05)"
+    );
+}
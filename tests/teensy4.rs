@@ -114,6 +114,28 @@ fn teensy4() {
     assert_eq!(count, 128 / 16);
 }
 
+#[test]
+fn teensy4_to_bytes() {
+    const BYTES: [u8; 512] = SERIAL_NOR_CONFIGURATION_BLOCK.to_bytes();
+    let actual: Vec<u32> = BYTES
+        .chunks_exact(4)
+        .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+        .collect();
+    assert_eq!(actual, EXPECTED);
+}
+
+#[test]
+fn teensy4_decode() {
+    let mut bytes = [0; 512];
+    for (dst, src) in bytes.chunks_exact_mut(4).zip(EXPECTED.iter()) {
+        dst.copy_from_slice(&src.to_le_bytes());
+    }
+    assert_eq!(
+        nor::ConfigurationBlock::from_bytes(&bytes),
+        Ok(SERIAL_NOR_CONFIGURATION_BLOCK)
+    );
+}
+
 // A known, working FCB for the Teensy 4.
 const EXPECTED: [u32; 128] = [
     // 448 byte common FlexSPI configuration block, 8.6.3.1 page 223 (RT1062 rev 0)